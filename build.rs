@@ -0,0 +1,54 @@
+//! Generates the list of solutions that get linked into the `solutions` binary.
+//! Every `src/bin/YYYY-DD.rs` file is included as a module and its `SOLUTION` registered.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut bins: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let bin = name.strip_suffix(".rs")?;
+                    let (year, day) = bin.split_once('-')?;
+                    let is_digits =
+                        |s: &str, len| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+                    (is_digits(year, 4) && is_digits(day, 2)).then(|| bin.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort();
+
+    let mut out = String::new();
+
+    for bin in &bins {
+        let path = bin_dir.join(format!("{bin}.rs"));
+        println!("cargo:rerun-if-changed={}", path.display());
+        out.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod {};\n\n",
+            path.display().to_string(),
+            module_name(bin)
+        ));
+    }
+
+    out.push_str("/// Every solution in `src/bin`, ordered by year and day.\n");
+    out.push_str("pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for bin in &bins {
+        out.push_str(&format!("    {}::SOLUTION,\n", module_name(bin)));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+/// The module name of a solution binary, e.g. `y2023_day_05` for `2023-05`.
+fn module_name(bin: &str) -> String {
+    format!("y{}", bin.replace('-', "_day_"))
+}
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use regex::Regex;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2023, 1);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut calibrations: Vec<u32> = vec![];
@@ -1,6 +1,6 @@
 use std::cmp::max;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 const MAX_RED: u32 = 12;
 const MAX_GREEN: u32 = 13;
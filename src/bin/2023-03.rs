@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 fn is_symbol(x: &char) -> bool {
     x != &'.' && x.is_ascii_punctuation()
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4);
 
 #[derive(Debug)]
 struct Card<'a> {
@@ -17,7 +17,7 @@ impl<'a> Scorable for Card<'a> {
     fn points(&self) -> u32 {
         self.card_nums
             .iter()
-            .filter(|num| self.win_nums.get(*num).is_some())
+            .filter(|num| self.win_nums.contains_key(*num))
             .collect::<Vec<&&str>>()
             .len()
             .try_into()
@@ -25,7 +25,7 @@ impl<'a> Scorable for Card<'a> {
     }
 }
 
-fn as_card(line: &str) -> Card {
+fn as_card(line: &str) -> Card<'_> {
     let (card, numbers) = line.split_once(": ").unwrap();
     let card_number: u32 = card.strip_prefix("Card ").unwrap().trim().parse().unwrap();
 
@@ -0,0 +1,697 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+
+use parsers::{parse_input, parse_input_2, SeedTuples};
+
+advent_of_code::solution!(2023, 5);
+
+mod parsers {
+
+    use nom::{
+        branch::alt,
+        bytes::complete::{tag, take_until},
+        character::complete::{digit1, line_ending, newline, space0, space1},
+        combinator::{eof, map_res},
+        multi::{many0, many1},
+        sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+        IResult,
+    };
+
+    /// Parses a number, failing for numbers that don't fit into an u64 instead of wrapping around.
+    fn parse_u64(input: &str) -> IResult<&str, u64> {
+        map_res(digit1, str::parse)(input)
+    }
+
+    pub fn parse_header(input: &str) -> IResult<&str, Vec<u64>> {
+        let numbers = many1(delimited(space0, parse_u64, space0));
+        preceded(pair(tag("seeds:"), space1), numbers)(input)
+    }
+
+    pub type ChartTitle<'a> = (&'a str, &'a str);
+    pub type Mapping = (u64, u64, u64);
+    pub type SeedsToPlant = Vec<u64>;
+    pub type Chart<'a> = (ChartTitle<'a>, Vec<Mapping>);
+    pub fn parse_map(input: &str) -> IResult<&str, (ChartTitle<'_>, Vec<Mapping>)> {
+        let from = take_until("-");
+        let to = take_until(" ");
+        let delimited_u64 = |i| delimited(space0, parse_u64, space0)(i);
+        pair(
+            terminated(
+                separated_pair(from, tag("-to-"), to),
+                pair(tag(" map:"), line_ending),
+            ),
+            many1(terminated(
+                tuple((delimited_u64, delimited_u64, delimited_u64)),
+                alt((line_ending, eof)),
+            )),
+        )(input)
+    }
+
+    pub fn parse_body(input: &str) -> IResult<&str, Vec<Chart<'_>>> {
+        many1(delimited(many0(line_ending), parse_map, many0(line_ending)))(input)
+    }
+
+    pub fn parse_input(input: &str) -> IResult<&str, (SeedsToPlant, Vec<Chart<'_>>)> {
+        separated_pair(parse_header, many0(newline), parse_body)(input)
+    }
+
+    pub fn parse_header_2(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
+        let numbers = many1(delimited(
+            alt((line_ending, space0)),
+            separated_pair(parse_u64, space1, parse_u64),
+            alt((line_ending, space0)),
+        ));
+
+        preceded(pair(tag("seeds:"), space1), numbers)(input)
+    }
+
+    pub type SeedTuples = Vec<(u64, u64)>;
+    pub fn parse_input_2(input: &str) -> IResult<&str, (SeedTuples, Vec<Chart<'_>>)> {
+        separated_pair(parse_header_2, many0(line_ending), parse_body)(input)
+    }
+}
+
+/// A half-open range of numbers, e.g. a range of seeds.
+type Interval = Range<u64>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AlmanacError {
+    /// The almanac could not be parsed, holds the line that failed to parse.
+    Parse(String),
+    /// A range of the almanac ends beyond [`u64::MAX`].
+    Overflow { start: u64, length: u64 },
+    /// The almanac does not list any seeds, so there is no lowest location.
+    NoSeeds,
+    /// The almanac has more than one chart from a category.
+    DuplicateChart(String),
+    /// The charts of the almanac don't lead from one category to the other.
+    NoConversion { from: String, to: String },
+}
+
+impl Display for AlmanacError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlmanacError::Parse(line) => write!(f, "could not parse the almanac at `{line}`"),
+            AlmanacError::Overflow { start, length } => write!(
+                f,
+                "the range of {length} numbers starting at {start} ends beyond {}",
+                u64::MAX
+            ),
+            AlmanacError::NoSeeds => f.write_str("the almanac does not list any seeds"),
+            AlmanacError::DuplicateChart(from) => {
+                write!(f, "the almanac has more than one chart from `{from}`")
+            }
+            AlmanacError::NoConversion { from, to } => {
+                write!(f, "the almanac can not convert `{from}` to `{to}`")
+            }
+        }
+    }
+}
+
+impl From<nom::Err<nom::error::Error<&str>>> for AlmanacError {
+    fn from(e: nom::Err<nom::error::Error<&str>>) -> Self {
+        let line = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.input.lines().next().unwrap_or(""),
+            nom::Err::Incomplete(_) => "",
+        };
+        AlmanacError::Parse(line.into())
+    }
+}
+
+/// Creates the range of `length` numbers starting at `start`, if it ends within [`u64::MAX`].
+fn interval(start: u64, length: u64) -> Result<Interval, AlmanacError> {
+    start
+        .checked_add(length)
+        .map(|end| start..end)
+        .ok_or(AlmanacError::Overflow { start, length })
+}
+
+struct Chart {
+    /// Mappings ordered by their source, which lets ranges be split in a single pass.
+    mappings: Vec<ChartMapping>,
+}
+
+impl Chart {
+    fn new(mut mappings: Vec<ChartMapping>) -> Self {
+        // empty mappings don't convert any number.
+        mappings.retain(|x| x.range > 0);
+        mappings.sort_unstable_by_key(|x| x.source);
+        Self { mappings }
+    }
+
+    #[cfg(test)]
+    fn convert(&self, num: u64) -> u64 {
+        self.mappings
+            .iter()
+            .find_map(|x| x.convert(num))
+            .unwrap_or(num)
+    }
+
+    /// Splits a range of numbers wherever it crosses the boundary of a mapping.
+    /// Returns every part with the number its start converts to, parts that no mapping covers keep their numbers.
+    fn split(&self, range: Interval) -> Vec<(Interval, u64)> {
+        let mut parts = vec![];
+        let mut start = range.start;
+
+        for mapping in &self.mappings {
+            if start >= range.end || mapping.source >= range.end {
+                break;
+            }
+
+            if mapping.source_end() <= start {
+                continue;
+            }
+
+            if start < mapping.source {
+                parts.push((start..mapping.source, start));
+                start = mapping.source;
+            }
+
+            let end = range.end.min(mapping.source_end());
+            parts.push((start..end, mapping.convert(start).unwrap()));
+            start = end;
+        }
+
+        if start < range.end {
+            parts.push((start..range.end, start));
+        }
+
+        parts
+    }
+
+    /// Converts a range of numbers, see [`Chart::split`].
+    fn convert_range(&self, range: Interval) -> Vec<Interval> {
+        self.split(range)
+            .into_iter()
+            .map(|(part, dest)| dest..dest + (part.end - part.start))
+            .collect()
+    }
+
+    fn convert_ranges(&self, ranges: &[Interval]) -> Vec<Interval> {
+        ranges
+            .iter()
+            .flat_map(|x| self.convert_range(x.clone()))
+            .collect()
+    }
+
+    /// Finds the numbers that convert into `range`, the inverse of [`Chart::convert_range`].
+    /// Numbers that no mapping covers convert to themselves, so they are part of the result when they fall within `range`.
+    fn preimage(&self, range: Interval) -> Vec<Interval> {
+        let unmapped = self
+            .split(range.clone())
+            .into_iter()
+            .filter(|(part, dest)| part.start == *dest)
+            .map(|(part, _)| part);
+
+        let mapped = self.mappings.iter().filter_map(|mapping| {
+            let inverse = mapping.inverse();
+            let start = range.start.max(inverse.source);
+            let end = range.end.min(inverse.source_end());
+            (start < end).then(|| {
+                let source = inverse.convert(start).unwrap();
+                source..source + (end - start)
+            })
+        });
+
+        merge(unmapped.chain(mapped).collect())
+    }
+
+    fn preimage_ranges(&self, ranges: &[Interval]) -> Vec<Interval> {
+        merge(
+            ranges
+                .iter()
+                .flat_map(|x| self.preimage(x.clone()))
+                .collect(),
+        )
+    }
+
+    /// Composes this chart with the chart that converts the numbers this chart converts to.
+    fn then(&self, next: &Chart) -> Chart {
+        let mut mappings = vec![];
+
+        for (source, dest) in self.split(0..u64::MAX) {
+            let length = source.end - source.start;
+
+            for (part, next_dest) in next.split(dest..dest + length) {
+                let start = source.start + (part.start - dest);
+
+                // numbers that keep their value don't need a mapping.
+                if start != next_dest {
+                    mappings.push(ChartMapping {
+                        source: start,
+                        dest: next_dest,
+                        range: part.end - part.start,
+                    });
+                }
+            }
+        }
+
+        Chart::new(mappings)
+    }
+}
+
+/// Sorts ranges and joins the ones that overlap or touch.
+fn merge(mut ranges: Vec<Interval>) -> Vec<Interval> {
+    ranges.retain(|x| !x.is_empty());
+    ranges.sort_unstable_by_key(|x| x.start);
+
+    let mut merged: Vec<Interval> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// The charts of an almanac as a graph of categories, keyed by the category they convert from.
+/// Every category converts to at most one other, e.g. `seed` to `soil`.
+pub struct Almanac<'a> {
+    charts: HashMap<&'a str, (&'a str, Chart)>,
+}
+
+impl<'a> Almanac<'a> {
+    fn new(charts: Vec<parsers::Chart<'a>>) -> Result<Self, AlmanacError> {
+        let mut graph = HashMap::new();
+
+        for ((from, to), mappings) in charts {
+            let mappings = mappings
+                .into_iter()
+                .map(ChartMapping::try_from)
+                .collect::<Result<_, _>>()?;
+
+            if graph.insert(from, (to, Chart::new(mappings))).is_some() {
+                return Err(AlmanacError::DuplicateChart(from.into()));
+            }
+        }
+
+        Ok(Self { charts: graph })
+    }
+
+    /// Finds the charts that convert `from` to `to`, in the order they have to be applied.
+    fn route(&self, from: &str, to: &str) -> Result<Vec<&Chart>, AlmanacError> {
+        let mut route = vec![];
+        let mut category = from;
+
+        while category != to {
+            // a route that is longer than the number of charts runs in circles.
+            let Some((next, chart)) = self
+                .charts
+                .get(category)
+                .filter(|_| route.len() < self.charts.len())
+            else {
+                return Err(AlmanacError::NoConversion {
+                    from: from.into(),
+                    to: to.into(),
+                });
+            };
+
+            route.push(chart);
+            category = next;
+        }
+
+        Ok(route)
+    }
+
+    /// Composes the charts that convert `from` to `to` into a single chart.
+    fn conversion(&self, from: &str, to: &str) -> Result<Chart, AlmanacError> {
+        let route = self.route(from, to)?;
+        Ok(route
+            .into_iter()
+            .fold(Chart::new(vec![]), |chart, next| chart.then(next)))
+    }
+
+    /// Finds the ranges of seeds that are planted at `locations`, the reverse of converting seeds to locations.
+    pub fn seeds_for_locations(
+        &self,
+        locations: &[Interval],
+    ) -> Result<Vec<Interval>, AlmanacError> {
+        Ok(self
+            .conversion("seed", "location")?
+            .preimage_ranges(locations))
+    }
+}
+
+/// A mapping of a range of numbers, both its source and destination range end within [`u64::MAX`].
+struct ChartMapping {
+    source: u64,
+    dest: u64,
+    range: u64,
+}
+
+impl ChartMapping {
+    /// The end of the source range, exclusive.
+    fn source_end(&self) -> u64 {
+        self.source + self.range
+    }
+
+    /// The mapping that converts the destination range back to the source range.
+    fn inverse(&self) -> ChartMapping {
+        ChartMapping {
+            source: self.dest,
+            dest: self.source,
+            range: self.range,
+        }
+    }
+
+    fn in_range(&self, num: u64) -> bool {
+        self.source <= num && num - self.source < self.range
+    }
+
+    fn convert(&self, num: u64) -> Option<u64> {
+        self.in_range(num).then(|| {
+            // get difference
+            let diff = num - self.source;
+
+            self.dest + diff
+        })
+    }
+}
+
+impl TryFrom<(u64, u64, u64)> for ChartMapping {
+    type Error = AlmanacError;
+
+    fn try_from(tuple: (u64, u64, u64)) -> Result<Self, Self::Error> {
+        let (dest, source, range) = tuple;
+        interval(dest, range)?;
+        interval(source, range)?;
+
+        Ok(Self {
+            dest,
+            source,
+            range,
+        })
+    }
+}
+
+fn prepare(input: &str) -> Result<(Vec<u64>, Almanac<'_>), AlmanacError> {
+    let (_, (seeds, charts)) = parse_input(input)?;
+    Ok((seeds, Almanac::new(charts)?))
+}
+
+fn prepare_2(input: &str) -> Result<(SeedTuples, Almanac<'_>), AlmanacError> {
+    let (_, (seeds, charts)) = parse_input_2(input)?;
+    Ok((seeds, Almanac::new(charts)?))
+}
+
+/// Converts ranges of seeds to locations and returns the lowest location.
+fn lowest_location(seeds: &[Interval], almanac: &Almanac) -> Result<u64, AlmanacError> {
+    almanac
+        .conversion("seed", "location")?
+        .convert_ranges(seeds)
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .map(|x| x.start)
+        .min()
+        .ok_or(AlmanacError::NoSeeds)
+}
+
+pub fn part_one(input: &str) -> Result<u64, AlmanacError> {
+    let (seeds, almanac) = prepare(input)?;
+    let seeds: Vec<Interval> = seeds
+        .into_iter()
+        .map(|seed| interval(seed, 1))
+        .collect::<Result<_, _>>()?;
+
+    lowest_location(&seeds, &almanac)
+}
+
+pub fn part_two(input: &str) -> Result<u64, AlmanacError> {
+    let (seeds, almanac) = prepare_2(input)?;
+    let seeds: Vec<Interval> = seeds
+        .into_iter()
+        .map(|(start, range)| interval(start, range))
+        .collect::<Result<_, _>>()?;
+
+    lowest_location(&seeds, &almanac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parsers::*;
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        let (input, nums) = parse_header(EXAMPLE).unwrap();
+        println!("{}", input);
+        assert_eq!(nums, [79, 14, 55, 13]);
+    }
+
+    #[test]
+    fn test_parse_map() {
+        let (input, ((from, to), mappings)) = parse_map(
+            "abc-to-xyzhjkl map:
+1 2 3
+4 5 6
+",
+        )
+        .unwrap();
+
+        assert_eq!(input, "");
+        assert_eq!(from, "abc");
+        assert_eq!(to, "xyzhjkl");
+        assert_eq!(mappings, [(1, 2, 3), (4, 5, 6)]);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let (input, (seeds, maps)) = parse_input(EXAMPLE).unwrap();
+
+        // not exhaustive testing but should be enough to check what we need to
+        assert_eq!(input, "");
+        assert_eq!(seeds, [79, 14, 55, 13]);
+        assert_eq!(maps.len(), 7);
+        assert_eq!(maps.first().unwrap().0, ("seed", "soil"));
+        assert_eq!(maps.last().unwrap().0, ("humidity", "location"));
+        assert_eq!(
+            maps.last().unwrap().1.first().unwrap(),
+            &(60u64, 56u64, 37u64)
+        );
+    }
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_convert_range() {
+        let mappings =
+            [(52, 50, 48), (50, 98, 2), (0, 70, 0)].map(|x| ChartMapping::try_from(x).unwrap());
+        let chart = super::Chart::new(mappings.into());
+
+        // splits at both boundaries of a mapping.
+        assert_eq!(chart.convert_range(40..60), [40..50, 52..62]);
+        assert_eq!(chart.convert_range(90..105), [92..100, 50..52, 100..105]);
+        assert_eq!(chart.convert_range(0..10), [0..10]);
+
+        // converting a single number is the same as converting a range of it.
+        for seed in 40..105 {
+            let converted = chart.convert_range(seed..seed + 1);
+            assert_eq!(converted, [chart.convert(seed)..chart.convert(seed) + 1]);
+        }
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(EXAMPLE);
+        assert_eq!(result, Ok(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(EXAMPLE);
+        assert_eq!(result, Ok(46));
+    }
+
+    #[test]
+    fn test_route() {
+        let (_, almanac) = prepare(EXAMPLE).unwrap();
+
+        assert_eq!(almanac.route("light", "humidity").unwrap().len(), 2);
+        assert_eq!(almanac.route("seed", "location").unwrap().len(), 7);
+        assert_eq!(almanac.route("soil", "soil").unwrap().len(), 0);
+        assert_eq!(
+            almanac.route("location", "seed").err(),
+            Some(AlmanacError::NoConversion {
+                from: "location".into(),
+                to: "seed".into()
+            })
+        );
+
+        // the chain from seeds to locations is broken.
+        let broken = EXAMPLE.replace("water-to-light", "water-to-lamp");
+        assert_eq!(
+            part_one(&broken),
+            Err(AlmanacError::NoConversion {
+                from: "seed".into(),
+                to: "location".into()
+            })
+        );
+
+        let looped = EXAMPLE.replace("humidity-to-location", "humidity-to-seed");
+        assert_eq!(
+            part_one(&looped),
+            Err(AlmanacError::NoConversion {
+                from: "seed".into(),
+                to: "location".into()
+            })
+        );
+
+        let duplicate = format!("{EXAMPLE}\n\nseed-to-soil map:\n1 2 3");
+        assert_eq!(
+            part_one(&duplicate),
+            Err(AlmanacError::DuplicateChart("seed".into()))
+        );
+    }
+
+    #[test]
+    fn test_conversion() {
+        let (_, almanac) = prepare(EXAMPLE).unwrap();
+        let charts = almanac.route("seed", "location").unwrap();
+        let composed = almanac.conversion("seed", "location").unwrap();
+
+        // the composed chart converts like applying every chart in turn.
+        for seed in 0..120 {
+            let location = charts.iter().fold(seed, |n, chart| chart.convert(n));
+            assert_eq!(composed.convert(seed), location);
+        }
+
+        let light = almanac.conversion("light", "humidity").unwrap();
+        assert_eq!(light.convert(74), 78);
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_preimage() {
+        let mappings = [(50, 98, 2), (52, 50, 48)].map(|x| ChartMapping::try_from(x).unwrap());
+        let chart = super::Chart::new(mappings.into());
+
+        // 50 and 51 are converted away, 98 and 99 convert to them.
+        assert_eq!(chart.preimage(40..52), [40..50, 98..100]);
+        assert_eq!(chart.preimage(52..54), [50..52]);
+        assert_eq!(chart.preimage(100..110), [100..110]);
+        assert_eq!(chart.preimage(98..100), [96..98]);
+        assert_eq!(chart.preimage_ranges(&[0..10, 10..20]), [0..20]);
+    }
+
+    #[test]
+    fn test_preimage_matches_convert() {
+        let (_, almanac) = prepare(EXAMPLE).unwrap();
+        let chart = almanac.conversion("seed", "location").unwrap();
+
+        for range in [0..10, 35..47, 46..47, 60..100] {
+            let seeds = chart.preimage(range.clone());
+            for seed in 0..120 {
+                let converts_into = range.contains(&chart.convert(seed));
+                assert_eq!(seeds.iter().any(|x| x.contains(&seed)), converts_into);
+            }
+        }
+    }
+
+    /// Cross-checks the lowest location of both parts with the reverse lookup.
+    fn check_lowest_location(input: &str) {
+        let (seeds, almanac) = prepare(input).unwrap();
+        let seeds_1: Vec<Interval> = seeds.into_iter().map(|x| x..x + 1).collect();
+        let (seeds, _) = prepare_2(input).unwrap();
+        let seeds_2: Vec<Interval> = seeds.into_iter().map(|(x, n)| x..x + n).collect();
+
+        let chart = almanac.conversion("seed", "location").unwrap();
+        let planted = |locations: Interval, seeds: &[Interval]| {
+            let planted = almanac.seeds_for_locations(&[locations]).unwrap();
+            planted
+                .iter()
+                .any(|x| seeds.iter().any(|y| x.start < y.end && y.start < x.end))
+        };
+
+        for (seeds, lowest) in [(seeds_1, part_one(input)), (seeds_2, part_two(input))] {
+            let lowest = lowest.unwrap();
+
+            // some seed is planted at the lowest location, none below it.
+            assert!(planted(lowest..lowest + 1, &seeds));
+            assert!(!planted(0..lowest, &seeds));
+            assert!(seeds.iter().all(|x| chart.convert(x.start) >= lowest));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_seeds_for_locations() {
+        let (_, almanac) = prepare(EXAMPLE).unwrap();
+
+        // seed 82 is planted at location 46, seeds 79 and 80 at the next two locations.
+        assert_eq!(almanac.seeds_for_locations(&[46..47]).unwrap(), [82..83]);
+        assert_eq!(almanac.seeds_for_locations(&[82..84]).unwrap(), [79..81]);
+        check_lowest_location(EXAMPLE);
+    }
+
+    fn almanac(seeds: &str, mapping: &str) -> String {
+        format!("seeds: {seeds}\n\nseed-to-location map:\n{mapping}")
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let input = almanac("4294967301 4294967296", "5000000000 4294967300 10");
+
+        assert_eq!(part_one(&input), Ok(4294967296));
+        // seeds up to 4294967309 are mapped above the rest of the range.
+        assert_eq!(part_two(&input), Ok(4294967310));
+        check_lowest_location(&input);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            part_one(&almanac("1 2", "18446744073709551610 0 10")),
+            Err(AlmanacError::Overflow {
+                start: 18446744073709551610,
+                length: 10
+            })
+        );
+        assert_eq!(
+            part_two(&almanac("18446744073709551615 1", "0 10 5")),
+            Err(AlmanacError::Overflow {
+                start: 18446744073709551615,
+                length: 1
+            })
+        );
+        assert_eq!(
+            part_one(&almanac("18446744073709551616 2", "0 10 5")),
+            Err(AlmanacError::Parse("18446744073709551616 2".into()))
+        );
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 use parsers::parse_input;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 mod parsers {
     use nom::{
@@ -63,60 +63,53 @@ fn prepare(input: &str) -> Zip<IntoIter<u64>, IntoIter<u64>> {
     zip(time, dist)
 }
 
-fn is_winning(hold: &u64, time: &u64, dist: &u64) -> bool {
+fn is_winning(hold: u64, time: u64, dist: u64) -> bool {
     let remain = time - hold;
 
-    &(hold * remain) > dist
+    // the product of two u64 always fits into a u128.
+    u128::from(hold) * u128::from(remain) > u128::from(dist)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let races = prepare(input);
+/// Counts the hold times that travel further than `dist` within `time`.
+///
+/// The distance `hold * (time - hold)` is symmetric around half the race and grows until then, so the winning hold
+/// times are the range between the first winning hold and its mirror, which is found by a binary search.
+fn ways_to_win(time: u64, dist: u64) -> u64 {
+    let half = time / 2;
 
-    // brute force approach
-    let result = races
-        .map(|race| {
-            // convert race into # of ways to win
-            let (time, dist) = race;
-
-            // 0 & race.time are guaranteed losses
-
-            let mut wins: u64 = 0;
-            // hand roll for loop for short-circuiting
-            for hold in 1..time {
-                if is_winning(&hold, &time, &dist) {
-                    wins += 1
-                } else if wins > 0 {
-                    break;
-                }
-            }
+    if !is_winning(half, time, dist) {
+        return 0;
+    }
 
-            wins
-        })
-        .product();
+    let (mut low, mut high) = (0, half);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if is_winning(mid, time, dist) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
 
-    Some(result)
+    time - 2 * low + 1
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, (time, dist)) = parsers::parse_input_2(input).unwrap();
-
-    // quadratic formula solution
-    // fastest, but can't be applied 1:1 to example data (smaller numbers), might still implement a binary search
-    // solution for both
+pub fn part_one(input: &str) -> Option<u64> {
+    let races = prepare(input);
 
-    let a = 1.;
-    let b = 0.0 - time as f64;
-    let c = dist as f64;
+    Some(races.map(|(time, dist)| ways_to_win(time, dist)).product())
+}
 
-    let x = ((0. - b) - (b.powf(2.) - (4. * a * c)).sqrt()) / (2.0 * a);
-    let y = ((0. - b) + (b.powf(2.) - (4. * a * c)).sqrt()) / (2.0 * a);
+pub fn part_two(input: &str) -> Option<u64> {
+    let (_, (time, dist)) = parsers::parse_input_2(input).unwrap();
 
-    Some((y.floor() as u64 + 1) - (x.ceil() as u64))
+    Some(ways_to_win(time, dist))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     const EXAMPLE: &str = "Time:      7  15   30
 Distance:  9  40  200";
@@ -132,4 +125,35 @@ Distance:  9  40  200";
         let result = part_two(EXAMPLE);
         assert_eq!(result, Some(71503));
     }
+
+    #[rstest]
+    #[case(7, 9, 4)]
+    // holding 10 or 20 ties the record, which doesn't win.
+    #[case(30, 200, 9)]
+    #[case(4, 4, 0)]
+    #[case(4, 3, 1)]
+    #[case(5, 6, 0)]
+    #[case(5, 5, 2)]
+    #[case(0, 0, 0)]
+    #[case(1, 0, 0)]
+    #[case(2, 0, 1)]
+    // too large for the roots to be exact as f64.
+    #[case(4_000_000_000, 3_999_999_999_999_999_999, 1)]
+    #[case(4_000_000_000, 4_000_000_000_000_000_000, 0)]
+    #[case(u64::MAX, u64::MAX, u64::MAX - 3)]
+    fn test_ways_to_win(#[case] time: u64, #[case] dist: u64, #[case] expected: u64) {
+        assert_eq!(ways_to_win(time, dist), expected);
+    }
+
+    #[test]
+    fn test_ways_to_win_brute_force() {
+        for time in 0..40 {
+            for dist in 0..400 {
+                let wins = (0..=time)
+                    .filter(|&hold| is_winning(hold, time, dist))
+                    .count();
+                assert_eq!(ways_to_win(time, dist), wins as u64, "{time} {dist}");
+            }
+        }
+    }
 }
@@ -2,7 +2,7 @@ use std::{cmp::Ordering, collections::HashMap};
 
 use parsers::parse_input;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 #[allow(dead_code)]
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Clone, Copy)]
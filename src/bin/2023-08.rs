@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 use parsers::parse_input;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8, parse);
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
@@ -24,7 +24,7 @@ pub struct NodeMap<'a> {
 }
 
 impl<'a> NodeMap<'a> {
-    fn navigate(&self, node: &Node, dir: &Direction) -> &Node {
+    fn navigate(&self, node: &Node, dir: &Direction) -> &Node<'_> {
         let label_to_find = match dir {
             Direction::Left => node.left_node,
             Direction::Right => node.right_node,
@@ -35,7 +35,7 @@ impl<'a> NodeMap<'a> {
         found.unwrap()
     }
 
-    fn get_bounds(&self) -> (&Node, &Node) {
+    fn get_bounds(&self) -> (&Node<'_>, &Node<'_>) {
         (
             self.nodes.get("AAA").unwrap(),
             self.nodes.get("ZZZ").unwrap(),
@@ -65,7 +65,7 @@ mod parsers {
         many1(dir)(input)
     }
 
-    pub fn parse_node(input: &str) -> IResult<&str, Node> {
+    pub fn parse_node(input: &str) -> IResult<&str, Node<'_>> {
         map(
             separated_pair(
                 alphanumeric1,
@@ -87,7 +87,7 @@ mod parsers {
         )(input)
     }
 
-    pub fn parse_input(input: &str) -> IResult<&str, (Vec<Direction>, HashMap<&str, Node>)> {
+    pub fn parse_input(input: &str) -> IResult<&str, (Vec<Direction>, HashMap<&str, Node<'_>>)> {
         separated_pair(
             parse_directions,
             many0(alt((line_ending, space1))),
@@ -104,7 +104,7 @@ mod parsers {
     }
 }
 
-fn create_map(input: &str) -> NodeMap {
+pub fn parse(input: &str) -> NodeMap<'_> {
     parse_input(input)
         .map(|(_, (dirs, nodes))| NodeMap { nodes, dirs })
         .unwrap()
@@ -112,8 +112,7 @@ fn create_map(input: &str) -> NodeMap {
 
 #[allow(unused_variables)]
 #[allow(unused_must_use)]
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = create_map(input);
+pub fn part_one(map: &NodeMap) -> Option<u32> {
     let (start, end) = map.get_bounds();
 
     let length = map.dirs.len();
@@ -137,9 +136,7 @@ fn lcm_of(nums: Vec<u64>) -> u64 {
 
 #[allow(unused_variables)]
 #[allow(unused_must_use)]
-pub fn part_two(input: &str) -> Option<u64> {
-    let map = create_map(input);
-
+pub fn part_two(map: &NodeMap) -> Option<u64> {
     let length = map.dirs.len();
 
     let paths: Vec<&Node> = map
@@ -218,16 +215,16 @@ ZZZ = (ZZZ, ZZZ)";
 
     #[test]
     fn test_part_one() {
-        let result = part_one(EXAMPLE);
+        let result = part_one(&parse(EXAMPLE));
         assert_eq!(result, Some(2));
 
-        let result2 = part_one(EXAMPLE_2);
+        let result2 = part_one(&parse(EXAMPLE_2));
         assert_eq!(result2, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(
+        let result = part_two(&parse(
             "LR
 
 11A = (11B, XXX)
@@ -238,7 +235,7 @@ ZZZ = (ZZZ, ZZZ)";
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)",
-        );
+        ));
 
         assert_eq!(result, Some(6));
     }
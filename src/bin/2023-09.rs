@@ -1,6 +1,6 @@
 use parsers::parse_input;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 mod parsers {
     use nom::{
@@ -1,30 +1,44 @@
 use std::error::Error;
 use std::fmt::Display;
-use std::str::FromStr;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+use crate::Year;
+
+/// The number of days of the longest events, and therefore the largest valid day number.
+pub const MAX_DAYS: u8 = 25;
+
+/// A valid day number of advent (i.e. an integer from 1 to the last day of an event).
 ///
 /// # Display
 /// This value displays as a two digit number.
 ///
 /// ```
-/// # use advent_of_code::Day;
-/// let day = Day::new(8).unwrap();
+/// # use advent_of_code::{Day, Year};
+/// let day = Day::new_in(Year::new(2023).unwrap(), 8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] from the provided value if it's a day of the event in `year`,
     /// returns [`None`] otherwise.
-    pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+    pub fn new_in(year: Year, day: u8) -> Option<Self> {
+        if day == 0 || day > year.days() {
             return None;
         }
         Some(Self(day))
     }
 
+    /// Parses a [`Day`] that has to be a day of the event in `year`.
+    pub fn parse_in(s: &str, year: Year) -> Result<Self, DayFromStrError> {
+        let error = DayFromStrError {
+            max: year.days(),
+            year,
+        };
+        let day = s.parse().map_err(|_| error.clone())?;
+        Self::new_in(year, day).ok_or(error)
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -57,43 +71,48 @@ impl PartialOrd<u8> for Day {
 
 /* -------------------------------------------------------------------------- */
 
-impl FromStr for Day {
-    type Err = DayFromStrError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
-    }
-}
-
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone)]
+pub struct DayFromStrError {
+    max: u8,
+    year: Year,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(
+            f,
+            "expecting a day number between 1 and {}, the {} event has {} days",
+            self.max, self.year, self.max
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
-}
-
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent, from the 1st to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    /// Creates an iterator over the days of the event in `year`.
+    pub fn of(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.days(),
+        }
+    }
+
+    /// Creates an iterator over the days from `first` to `last`, both inclusive.
+    pub fn between(first: Day, last: Day) -> Self {
+        Self {
+            current: first.0,
+            last: last.0,
+        }
     }
 }
 
@@ -101,10 +120,11 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at a valid day and `last` is never above the length of the event,
+        // see `Year::days`, which is at most `MAX_DAYS` even when overridden.
         let day = Day(self.current);
         self.current += 1;
 
@@ -114,16 +134,140 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
-/// Creates a [`Day`] value in a const context.
+/// A selection of days of an event, in ascending order and without duplicates.
+///
+/// Selections are parsed from a comma separated list of days and ranges, e.g. `1,4,9`, `3..=7` or `1,10..`.
+/// Ranges read like Rust's: `3..7` excludes day 7 while `3..=7` includes it, and a range without a start or end is
+/// bounded by the first or last day of the event.
+///
+/// ```
+/// # use advent_of_code::{DaySelection, Year};
+/// let year = Year::new(2023).unwrap();
+/// let days = DaySelection::parse_in("1,3..=5,10..12", year).unwrap();
+/// assert_eq!(days.to_string(), "1,3,4,5,10,11")
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(Vec<Day>);
+
+impl DaySelection {
+    /// Selects every day of the event in `year`.
+    pub fn all(year: Year) -> Self {
+        Self(AllDays::of(year).collect())
+    }
+
+    /// Parses a selection of days that have to be days of the event in `year`.
+    pub fn parse_in(s: &str, year: Year) -> Result<Self, DaySelectionFromStrError> {
+        let mut days = vec![];
+
+        for item in s.split(',').map(str::trim) {
+            let Some((first, last)) = item.split_once("..") else {
+                days.push(Day::parse_in(item, year)?);
+                continue;
+            };
+
+            let (last, is_inclusive) = match last.strip_prefix('=') {
+                Some(last) => (last, true),
+                None => (last, false),
+            };
+            let first = match first {
+                "" => 1,
+                first => Day::parse_in(first, year)?.0,
+            };
+            // the exclusive end of the range.
+            let end = match last {
+                "" => year.days() + 1,
+                last if is_inclusive => Day::parse_in(last, year)?.0 + 1,
+                // an exclusive range may end on the day after the last one.
+                last => match last.parse::<u8>() {
+                    Ok(end) if end == year.days() + 1 => end,
+                    _ => Day::parse_in(last, year)?.0,
+                },
+            };
+
+            if first >= end {
+                return Err(DaySelectionFromStrError::EmptyRange(item.into()));
+            }
+
+            days.extend(AllDays::between(Day(first), Day(end - 1)));
+        }
+
+        days.sort_unstable();
+        days.dedup();
+        Ok(Self(days))
+    }
+
+    /// Removes the days of `other` from this selection.
+    #[must_use]
+    pub fn without(mut self, other: &DaySelection) -> Self {
+        self.0.retain(|day| !other.contains(*day));
+        self
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    /// Whether every day of the event in `year` is selected.
+    pub fn is_all(&self, year: Year) -> bool {
+        *self == Self::all(year)
+    }
+
+    /// An iterator that yields the selected days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl Display for DaySelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days: Vec<String> = self.0.iter().map(|x| x.0.to_string()).collect();
+        f.write_str(&days.join(","))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug, Clone)]
+pub enum DaySelectionFromStrError {
+    Day(DayFromStrError),
+    EmptyRange(String),
+}
+
+impl From<DayFromStrError> for DaySelectionFromStrError {
+    fn from(e: DayFromStrError) -> Self {
+        DaySelectionFromStrError::Day(e)
+    }
+}
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySelectionFromStrError::Day(e) => e.fmt(f),
+            DaySelectionFromStrError::EmptyRange(range) => {
+                write!(f, "the range `{range}` does not contain any days")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Day`] of the event in a year in a const context, see [`Year::known_days`].
+///
+/// The day is checked against the known length of the event. Unlike [`Day::parse_in`] and [`Year::days`], this does
+/// not honour an `AOC_DAYS_YYYY` override, so it rejects days that an override adds and accepts days it removes.
 #[macro_export]
 macro_rules! day {
-    ($day:expr) => {{
+    ($year:expr, $day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::Year::known_days($year),
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, the ",
+                $year,
+                " event does not have this day"
             ),
         );
         $crate::Day::__new_unchecked($day)
@@ -134,11 +278,12 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{AllDays, Day, DaySelection};
+    use crate::{year, Year};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::of(year!(2023));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -167,6 +312,76 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn days_of_year() {
+        assert_eq!(AllDays::of(year!(2023)).count(), 25);
+        assert_eq!(AllDays::of(year!(2025)).last(), Some(Day(12)));
+        assert_eq!(Year::known_days(2024), 25);
+        assert_eq!(Year::known_days(2025), 12);
+
+        assert_eq!(Day::new_in(year!(2023), 20), Some(Day(20)));
+        assert_eq!(Day::new_in(year!(2025), 20), None);
+        assert_eq!(Day::new_in(year!(2025), 0), None);
+    }
+
+    #[test]
+    fn parse_in_year() {
+        assert_eq!(Day::parse_in("12", year!(2025)).unwrap(), Day(12));
+        assert_eq!(Day::parse_in("13", year!(2023)).unwrap(), Day(13));
+
+        let err = Day::parse_in("13", year!(2025)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expecting a day number between 1 and 12, the 2025 event has 12 days"
+        );
+        assert_eq!(
+            Day::parse_in("26", year!(2023)).unwrap_err().to_string(),
+            "expecting a day number between 1 and 25, the 2023 event has 25 days"
+        );
+    }
+
+    #[test]
+    fn parse_day_selection() {
+        let parse = |s| DaySelection::parse_in(s, year!(2023)).map(|x| x.to_string());
+
+        assert_eq!(parse("5").unwrap(), "5");
+        assert_eq!(parse("1,4,9").unwrap(), "1,4,9");
+        assert_eq!(parse("3..7").unwrap(), "3,4,5,6");
+        assert_eq!(parse("3..=5").unwrap(), "3,4,5");
+        assert_eq!(parse("9, 1..3, 2").unwrap(), "1,2,9");
+        assert_eq!(parse("..2,24..").unwrap(), "1,24,25");
+        assert_eq!(parse("..=2,23..26").unwrap(), "1,2,23,24,25");
+        assert_eq!(
+            DaySelection::parse_in("10..", year!(2025))
+                .unwrap()
+                .to_string(),
+            "10,11,12"
+        );
+
+        assert_eq!(
+            parse("7..3").unwrap_err().to_string(),
+            "the range `7..3` does not contain any days"
+        );
+        assert_eq!(
+            parse("3..3").unwrap_err().to_string(),
+            "the range `3..3` does not contain any days"
+        );
+        assert_eq!(parse("3..=3").unwrap(), "3");
+        assert_eq!(parse("1..27").is_err(), true);
+        assert_eq!(parse("1..=26").is_err(), true);
+        assert_eq!(parse("1,,2").is_err(), true);
+    }
+
+    #[test]
+    fn skip_days() {
+        let days = DaySelection::parse_in("1..=6", year!(2023)).unwrap();
+        let skip = DaySelection::parse_in("2,5", year!(2023)).unwrap();
+
+        assert_eq!(days.is_all(year!(2023)), false);
+        assert_eq!(days.without(&skip).to_string(), "1,3,4,6");
+        assert_eq!(DaySelection::all(year!(2023)).is_all(year!(2023)), true);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
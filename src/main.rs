@@ -1,56 +1,246 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench_compare, download, examples, read, scaffold, solve, verify,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{env, ffi::OsString, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            bench_export::{Export, ExportFormat},
+            bench_history::{self, Comparison},
+            input::InputSource,
+            report::Format,
+        },
+        Day, DaySelection, Year,
+    };
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
+            day: Day,
+        },
+        Examples {
+            year: Year,
             day: Day,
+            force: bool,
         },
         Scaffold {
+            year: Year,
             day: Day,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            format: Format,
+            input: InputSource,
         },
         All {
+            year: Year,
+            days: DaySelection,
             release: bool,
             time: bool,
+            fail_fast: bool,
+            jobs: usize,
+            format: Format,
+            compare: Option<Comparison>,
+            save_baseline: Option<String>,
+            export: Option<Export>,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
+            release: bool,
         },
+        BenchCompare {
+            year: Year,
+            rev: String,
+            days: DaySelection,
+        },
+    }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let example = args.contains("--example");
+        let input: Option<String> = args.opt_value_from_str("--input")?;
+
+        match (example, input) {
+            (true, Some(_)) => Err("--example and --input can not be used together".into()),
+            (true, None) => Ok(InputSource::Example),
+            (false, Some(path)) if path == "-" => Ok(InputSource::Stdin),
+            (false, Some(path)) => Ok(InputSource::Path(path.into())),
+            (false, None) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Parses `--compare`, which is implied by naming a `--baseline` to compare against.
+    fn parse_comparison(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Comparison>, Box<dyn std::error::Error>> {
+        let compare = args.contains("--compare");
+        let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+        let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+
+        if threshold.is_some_and(|x| x.is_nan() || x <= 0.0) {
+            return Err("--threshold must be a positive percentage".into());
+        }
+
+        if !compare && baseline.is_none() {
+            if threshold.is_some() {
+                return Err("--threshold can only be used with --compare".into());
+            }
+            return Ok(None);
+        }
+
+        Ok(Some(Comparison {
+            baseline,
+            threshold: threshold.unwrap_or(bench_history::DEFAULT_THRESHOLD),
+        }))
+    }
+
+    /// Parses the optional selection of days and the days to `--skip`, every day of the event is selected by default.
+    fn parse_day_selection(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<DaySelection, Box<dyn std::error::Error>> {
+        let skip = parse_skip(args, year)?;
+        select_days(args.opt_free_from_str()?, &skip, year)
+    }
+
+    /// Parses the days to `--skip`. Options have to be parsed before free arguments.
+    fn parse_skip(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Vec<DaySelection>, Box<dyn std::error::Error>> {
+        let skip = args
+            .values_from_str::<_, String>("--skip")?
+            .iter()
+            .map(|x| DaySelection::parse_in(x, year))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(skip)
+    }
+
+    fn select_days(
+        days: Option<String>,
+        skip: &[DaySelection],
+        year: Year,
+    ) -> Result<DaySelection, Box<dyn std::error::Error>> {
+        let days = match days {
+            Some(days) => DaySelection::parse_in(&days, year)?,
+            None => DaySelection::all(year),
+        };
+        let days = skip.iter().fold(days, |days, skip| days.without(skip));
+
+        if days.iter().next().is_none() {
+            return Err("no days selected".into());
+        }
+
+        Ok(days)
+    }
+
+    /// Takes `--export <format> <path>` from the raw arguments, as pico-args only parses options with one value.
+    fn take_export(args: &mut Vec<OsString>) -> Result<Option<Export>, Box<dyn std::error::Error>> {
+        let Some(i) = args.iter().position(|x| x == "--export") else {
+            return Ok(None);
+        };
+
+        let values: Vec<OsString> = args.drain(i..args.len().min(i + 3)).skip(1).collect();
+        let [format, path] = values.as_slice() else {
+            return Err(
+                "--export expects a format and a path, e.g. `--export html benchmarks.html`".into(),
+            );
+        };
+
+        let format: ExportFormat = format.to_string_lossy().parse()?;
+        Ok(Some(Export {
+            format,
+            path: path.into(),
+        }))
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut raw_args: Vec<OsString> = env::args_os().skip(1).collect();
+        let export = take_export(&mut raw_args)?;
+        let mut args = pico_args::Arguments::from_vec(raw_args);
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        if export.is_some() && subcommand.as_deref() != Some("all") {
+            return Err("--export can only be used with `all` and `time`".into());
+        }
+
+        let year: Year = match args.opt_value_from_str("--year")? {
+            Some(year) => year,
+            None => Year::from_env().ok_or("no year selected, pass --year or set AOC_YEAR")?,
+        };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                fail_fast: args.contains("--fail-fast"),
+                jobs: match args.opt_value_from_str(["-j", "--jobs"])? {
+                    Some(0) => return Err("--jobs must be at least 1".into()),
+                    jobs => jobs.unwrap_or(1),
+                },
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                compare: parse_comparison(&mut args)?,
+                save_baseline: args.opt_value_from_str("--save-baseline")?,
+                export,
+                // free arguments are parsed last, after every option has been taken.
+                days: parse_day_selection(&mut args, year)?,
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                year,
+                day: Day::parse_in(&args.free_from_str::<String>()?, year)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                year,
+                day: Day::parse_in(&args.free_from_str::<String>()?, year)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                year,
+                day: Day::parse_in(&args.free_from_str::<String>()?, year)?,
+                force: args.contains("--force"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                year,
+                day: Day::parse_in(&args.free_from_str::<String>()?, year)?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                year,
+                day: Day::parse_in(&args.free_from_str::<String>()?, year)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                input: parse_input_source(&mut args)?,
             },
+            Some("verify") => AppArguments::Verify {
+                year,
+                release: args.contains("--release"),
+                day: args
+                    .opt_free_from_str::<String>()?
+                    .map(|x| Day::parse_in(&x, year))
+                    .transpose()?,
+            },
+            Some("bench-compare") => {
+                let skip = parse_skip(&mut args, year)?;
+                AppArguments::BenchCompare {
+                    year,
+                    rev: args.free_from_str()?,
+                    days: select_days(args.opt_free_from_str()?, &skip, year)?,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -77,16 +267,48 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                days,
+                release,
+                time,
+                fail_fast,
+                jobs,
+                format,
+                compare,
+                save_baseline,
+                export,
+            } => all::handle(
+                year,
+                &days,
+                &all::Options {
+                    is_release: release,
+                    is_timed: time,
+                    is_fail_fast: fail_fast,
+                    jobs,
+                    format,
+                    compare,
+                    save_baseline,
+                    export,
+                },
+            ),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Examples { year, day, force } => examples::handle(year, day, force),
+            AppArguments::Scaffold { year, day } => scaffold::handle(year, day),
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                format,
+                input,
+            } => solve::handle(year, day, release, time, submit, format, &input),
+            AppArguments::Verify { year, day, release } => verify::handle(year, day, release),
+            AppArguments::BenchCompare { year, rev, days } => {
+                bench_compare::handle(year, &rev, &days);
+            }
         },
     };
 }
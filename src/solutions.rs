@@ -0,0 +1,79 @@
+//! Links every solution into a single binary, so that commands like `all` can run every day in-process.
+//! The list of solutions is generated by `build.rs` from the files in `src/bin`.
+use advent_of_code::template::bench_export::Export;
+use advent_of_code::template::bench_history::Comparison;
+use advent_of_code::template::commands::{all, verify};
+use advent_of_code::{Day, DaySelection};
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    let mut args = pico_args::Arguments::from_env();
+
+    let result = match args.subcommand() {
+        Ok(Some(command)) if command == "all" => args
+            .value_from_str("--year")
+            .and_then(|year| {
+                let days: Option<String> = args.opt_value_from_str("--days")?;
+                // the threshold is always passed along with --compare.
+                let compare = if args.contains("--compare") {
+                    Some(Comparison {
+                        baseline: args.opt_value_from_str("--baseline")?,
+                        threshold: args.value_from_str("--threshold")?,
+                    })
+                } else {
+                    None
+                };
+                let export = match args.opt_value_from_str("--export-format")? {
+                    Some(format) => Some(Export {
+                        format,
+                        path: args.value_from_str::<_, String>("--export-path")?.into(),
+                    }),
+                    None => None,
+                };
+                let options = all::Options {
+                    is_release: args.contains("--release"),
+                    is_timed: args.contains("--time"),
+                    is_fail_fast: args.contains("--fail-fast"),
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    compare,
+                    save_baseline: args.opt_value_from_str("--save-baseline")?,
+                    export,
+                };
+                Ok((year, days, options))
+            })
+            .map(|(year, days, options)| {
+                let days = match days {
+                    Some(days) => DaySelection::parse_in(&days, year).unwrap_or_else(|e| {
+                        eprintln!("Error: {e}");
+                        std::process::exit(1);
+                    }),
+                    None => DaySelection::all(year),
+                };
+                all::run(SOLUTIONS, year, &days, &options);
+            }),
+        Ok(Some(command)) if command == "verify" => args
+            .value_from_str("--year")
+            .and_then(|year| Ok((year, args.opt_free_from_str::<String>()?)))
+            .map(|(year, day)| {
+                let day = day.map(|x| {
+                    Day::parse_in(&x, year).unwrap_or_else(|e| {
+                        eprintln!("Error: {e}");
+                        std::process::exit(1);
+                    })
+                });
+                verify::run(SOLUTIONS, year, day);
+            }),
+        Ok(command) => {
+            eprintln!("Unknown command: {command:?}");
+            std::process::exit(1);
+        }
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,173 @@
+/// Module that stores the correct answers of solved parts in `data/answers/YYYY/DD.toml`.
+///
+/// The files are plain TOML and can be edited by hand, e.g. to record answers of days solved before.
+///
+/// ```toml
+/// part_1 = "35"
+/// part_2 = "46"
+/// ```
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::get_data_path;
+use crate::{Day, Year};
+
+/// The recorded answers for a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        let mut answers = Self::default();
+
+        for line in s.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            let Some(value) = unquote(value.trim()) else {
+                continue;
+            };
+
+            match key.trim() {
+                "part_1" => answers.part_1 = Some(value),
+                "part_2" => answers.part_2 = Some(value),
+                _ => {}
+            }
+        }
+
+        answers
+    }
+
+    fn serialize(&self) -> String {
+        [("part_1", &self.part_1), ("part_2", &self.part_2)]
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .as_ref()
+                    .map(|value| format!("{key} = {}\n", quote(value)))
+            })
+            .collect()
+    }
+}
+
+fn quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next()? {
+                'n' => out.push('\n'),
+                c => out.push(c),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    Some(out)
+}
+
+#[must_use]
+pub fn get_path_for_answers(year: Year, day: Day) -> PathBuf {
+    get_data_path("answers", year, &format!("{day}.toml"))
+}
+
+/// Loads the recorded answers for a day. Days without a file have no recorded answers.
+pub fn load(year: Year, day: Day) -> Result<Answers, io::Error> {
+    read(&get_path_for_answers(year, day))
+}
+
+/// Records the correct answer for a part, keeping the answer of the other part.
+pub fn save(year: Year, day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+    let mut answers = load(year, day)?;
+    answers.set(part, answer);
+    write(&get_path_for_answers(year, day), &answers)
+}
+
+/// Reads answers from a file in the format described above. A missing file contains no answers.
+pub fn read(path: &Path) -> Result<Answers, io::Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => Ok(Answers::parse(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes answers to a file in the format described above, creating parent directories as needed.
+pub fn write(path: &Path, answers: &Answers) -> Result<(), io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, answers.serialize())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("part_1 = \"35\"\n# comment\npart_2 = \"46\"\n");
+        assert_eq!(answers.get(1), Some("35"));
+        assert_eq!(answers.get(2), Some("46"));
+    }
+
+    #[test]
+    fn parse_partial_answers() {
+        let answers = Answers::parse("part_2 = \"abc\"\n");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("abc"));
+    }
+
+    #[test]
+    fn roundtrip_escaped_answers() {
+        let mut answers = Answers::default();
+        answers.set(1, "say \"hi\"\n#..#\\");
+
+        let parsed = Answers::parse(&answers.serialize());
+        assert_eq!(parsed, answers);
+    }
+
+    #[test]
+    fn serialize_answers() {
+        let mut answers = Answers::default();
+        answers.set(2, "46");
+        answers.set(1, "35");
+        assert_eq!(answers.serialize(), "part_1 = \"35\"\npart_2 = \"46\"\n");
+    }
+}
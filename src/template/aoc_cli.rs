@@ -1,10 +1,12 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
     process::{Command, Output, Stdio},
 };
 
-use crate::Day;
+use crate::template::get_data_path;
+use crate::{Day, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -35,8 +37,8 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(year: Year, day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(year, day)?;
 
     let args = build_args(
         "read",
@@ -45,15 +47,16 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
+        year,
         day,
     );
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(year: Year, day: Day) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(year, day)?;
+    let puzzle_path = get_puzzle_path(year, day)?;
 
     let args = build_args(
         "download",
@@ -64,6 +67,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
+        year,
         day,
     );
 
@@ -74,38 +78,54 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer. The output of aoc-cli is captured, callers print it.
+pub fn submit(year: Year, day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], year, day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Returns the path of a data file as a string, creating its directory so aoc-cli can write to it.
+fn get_file_path(folder: &str, year: Year, file_name: &str) -> Result<String, AocCommandError> {
+    let path = get_data_path(folder, year, file_name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|_| AocCommandError::IoError)?;
+    }
+    Ok(path.display().to_string())
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+fn get_input_path(year: Year, day: Day) -> Result<String, AocCommandError> {
+    get_file_path("inputs", year, &format!("{day}.txt"))
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+fn get_puzzle_path(year: Year, day: Day) -> Result<String, AocCommandError> {
+    get_file_path("puzzles", year, &format!("{day}.md"))
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], year: Year, day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        year.to_string(),
+        "--day".into(),
+        day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
@@ -0,0 +1,274 @@
+/// Native client for the Advent of Code website, used when aoc-cli is not installed.
+///
+/// The session token is read from the `AOC_SESSION` environment variable or the `~/.adventofcode.session` file.
+/// The base url defaults to `https://adventofcode.com` and can be overridden with `AOC_BASE_URL`.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{get_data_path, markdown};
+use crate::{Day, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/nomnivore/advent-of-code-2023-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Http(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session token found, set AOC_SESSION or create ~/.adventofcode.session."
+            ),
+            AocClientError::Http(e) => write!(f, "request failed: {e}"),
+            AocClientError::IoError(e) => {
+                write!(f, "could not write output files to file system: {e}")
+            }
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Http(e.to_string())
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Creates a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the puzzle input.
+    pub fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(year, day)))
+    }
+
+    /// Fetches the puzzle description and converts it to Markdown.
+    pub fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(year, day))?;
+        Ok(markdown::articles_to_markdown(&html, &self.base_url))
+    }
+
+    /// Submits an answer and returns the text of the response.
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(year, day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        let html = response.into_string()?;
+        Ok(markdown::articles_to_markdown(&html, &self.base_url))
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let session = fs::read_to_string(PathBuf::from(home).join(".adventofcode.session")).ok()?;
+    Some(session)
+}
+
+/// Writes a data file, creating its directory if needed.
+fn write_file(
+    folder: &str,
+    year: Year,
+    file_name: &str,
+    contents: &str,
+) -> Result<PathBuf, AocClientError> {
+    let path = get_data_path(folder, year, file_name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Downloads input and puzzle description of a day to the data directory.
+pub fn download(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+
+    let input = client.input(year, day)?;
+    let puzzle = client.puzzle(year, day)?;
+
+    let input_path = write_file("inputs", year, &format!("{day}.txt"), &input)?;
+    let puzzle_path = write_file("puzzles", year, &format!("{day}.md"), &puzzle)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Fetches the puzzle description of a day, saves it and prints it to the terminal.
+pub fn read(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.puzzle(year, day)?;
+    write_file("puzzles", year, &format!("{day}.md"), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// Submits an answer and returns the text of the response.
+pub fn submit(year: Year, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+    AocClient::from_env()?.submit(year, day, part, answer)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AocClient;
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves the given bodies to consecutive requests on a local port, returns the base url and the received requests.
+    fn serve(bodies: Vec<&'static str>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            bodies
+                .into_iter()
+                .map(|body| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = len.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+
+                    let mut content = vec![0; content_length];
+                    reader.read_exact(&mut content).unwrap();
+                    request.push_str(&String::from_utf8(content).unwrap());
+
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    reader.get_mut().write_all(response.as_bytes()).unwrap();
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, server) = serve(vec!["1abc2\n"]);
+        let client = AocClient::new(&base_url, "token\n");
+
+        assert_eq!(client.input(year!(2023), day!(2023, 1)).unwrap(), "1abc2\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].starts_with("GET /2023/day/1/input "), true);
+        assert_eq!(requests[0].contains("session=token\r\n"), true);
+    }
+
+    #[test]
+    fn fetches_puzzle() {
+        let (base_url, server) = serve(vec![
+            "<html><article class=\"day-desc\"><h2>--- Day 5 ---</h2><p>Hi <em>there</em>.</p></article></html>",
+        ]);
+        let client = AocClient::new(&base_url, "token");
+
+        assert_eq!(
+            client.puzzle(year!(2023), day!(2023, 5)).unwrap(),
+            "## --- Day 5 ---\n\nHi *there*."
+        );
+        assert_eq!(
+            server.join().unwrap()[0].starts_with("GET /2023/day/5 "),
+            true
+        );
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, server) = serve(vec![
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        ]);
+        let client = AocClient::new(&base_url, "token");
+
+        assert_eq!(
+            client.submit(year!(2023), day!(2023, 5), 2, "46").unwrap(),
+            "That's not the right answer; your answer is too high."
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].starts_with("POST /2023/day/5/answer "), true);
+        assert_eq!(requests[0].ends_with("level=2&answer=46"), true);
+    }
+}
@@ -0,0 +1,367 @@
+/// Module that exports the timings of `cargo time` as CSV, JSON or a standalone HTML report, so they can be charted.
+/// Exports are built from the same [`Timings`] as the benchmark table in the readme.
+use std::error::Error;
+use std::fmt::{Display, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{fs, io};
+
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::Timings;
+use crate::template::report::{durations_to_json, ordered_object, stringify, PARSE_PART};
+use crate::template::runner::Stats;
+use crate::Year;
+
+/// The width of the longest bar in the chart of the HTML report, in pixels.
+const CHART_WIDTH: f64 = 600.0;
+const CHART_ROW_HEIGHT: usize = 24;
+const CHART_LABEL_WIDTH: usize = 64;
+
+/// The parts of a day with their labels and chart colors.
+const PARTS: [(u8, &str, &str); 3] = [
+    (PARSE_PART, "Parse", "#7aa2c8"),
+    (1, "Part 1", "#9ca3af"),
+    (2, "Part 2", "#f2c84b"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Html,
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Csv => f.write_str("csv"),
+            ExportFormat::Json => f.write_str("json"),
+            ExportFormat::Html => f.write_str("html"),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(ExportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`ExportFormat`].
+#[derive(Debug)]
+pub struct ExportFormatFromStrError;
+
+impl Error for ExportFormatFromStrError {}
+
+impl Display for ExportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an export format of either \"csv\", \"json\" or \"html\"")
+    }
+}
+
+/// Where and how to export the timings of a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub format: ExportFormat,
+    pub path: PathBuf,
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn total(timings: &[Timings]) -> Duration {
+    timings
+        .iter()
+        .flat_map(|x| PARTS.iter().filter_map(|(part, ..)| x.get(*part)))
+        .map(|x| x.median)
+        .sum()
+}
+
+/// Formats one row per timed part, with durations in nanoseconds.
+fn to_csv(year: Year, timings: &[Timings]) -> String {
+    let mut lines = vec![
+        "year,day,part,median_ns,mean_ns,std_dev_ns,min_ns,p5_ns,p95_ns,max_ns,samples,outliers"
+            .to_string(),
+    ];
+
+    for timing in timings {
+        for (part, ..) in PARTS {
+            let Some(stats) = timing.get(part) else {
+                continue;
+            };
+
+            let part = match part {
+                PARSE_PART => "parse".to_string(),
+                part => part.to_string(),
+            };
+
+            lines.push(format!(
+                "{year},{},{part},{},{},{},{},{},{},{},{},{}",
+                timing.day.into_inner(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.std_dev.as_nanos(),
+                stats.min.as_nanos(),
+                stats.p5.as_nanos(),
+                stats.p95.as_nanos(),
+                stats.max.as_nanos(),
+                stats.samples,
+                stats.outliers,
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Formats the timings of every day as a JSON document, using the keys of the machine-readable reports.
+fn to_json(year: Year, timings: &[Timings]) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let part_to_json = |stats: Option<&Stats>| {
+        stats.map_or_else(
+            || stringify(JsonValue::Null),
+            |x| {
+                ordered_object(&[
+                    ("samples", stringify((x.samples as f64).into())),
+                    ("outliers", stringify((x.outliers as f64).into())),
+                    ("duration_ns", durations_to_json(x)),
+                ])
+            },
+        )
+    };
+
+    let days: Vec<String> = timings
+        .iter()
+        .map(|x| {
+            ordered_object(&[
+                ("day", stringify(f64::from(x.day.into_inner()).into())),
+                ("parse", part_to_json(x.parse.as_ref())),
+                ("part_1", part_to_json(x.part_1.as_ref())),
+                ("part_2", part_to_json(x.part_2.as_ref())),
+            ])
+        })
+        .collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let total_ns = total(timings).as_nanos() as f64;
+
+    let mut json = ordered_object(&[
+        ("year", stringify(f64::from(year.into_inner()).into())),
+        ("total_ns", stringify(total_ns.into())),
+        ("days", format!("[{}]", days.join(","))),
+    ]);
+    json.push('\n');
+    json
+}
+
+/// Formats a horizontal bar chart with one bar per day, stacked from the timings of its parts.
+/// Bars are scaled linearly to the slowest day, every bar is labelled with the total of its day.
+fn to_svg(timings: &[Timings]) -> String {
+    let day_total = |x: &Timings| -> Duration {
+        PARTS
+            .iter()
+            .filter_map(|(part, ..)| x.get(*part))
+            .map(|x| x.median)
+            .sum()
+    };
+
+    let max = timings.iter().map(day_total).max().unwrap_or_default();
+    let scale = |x: Duration| {
+        if max.is_zero() {
+            0.0
+        } else {
+            x.as_secs_f64() / max.as_secs_f64() * CHART_WIDTH
+        }
+    };
+
+    let legend_height = CHART_ROW_HEIGHT;
+    let width = CHART_LABEL_WIDTH + CHART_WIDTH as usize + 96;
+    let height = legend_height + timings.len() * CHART_ROW_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" role="img" aria-label="Timings per day">"#
+    );
+
+    let mut x = CHART_LABEL_WIDTH;
+    for (_, label, color) in PARTS {
+        let _ = writeln!(
+            svg,
+            r#"  <rect x="{x}" y="4" width="12" height="12" fill="{color}"/><text x="{}" y="14">{label}</text>"#,
+            x + 16
+        );
+        x += 80;
+    }
+
+    for (i, timing) in timings.iter().enumerate() {
+        let y = legend_height + i * CHART_ROW_HEIGHT;
+        let _ = writeln!(
+            svg,
+            r#"  <text x="0" y="{}">Day {}</text>"#,
+            y + 16,
+            timing.day
+        );
+
+        let mut x = CHART_LABEL_WIDTH as f64;
+        for (part, label, color) in PARTS {
+            let Some(stats) = timing.get(part) else {
+                continue;
+            };
+
+            let width = scale(stats.median);
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{x:.1}" y="{}" width="{width:.1}" height="{}" fill="{color}"><title>Day {} {label}: {:.1?}</title></rect>"#,
+                y + 4,
+                CHART_ROW_HEIGHT - 8,
+                timing.day,
+                stats.median
+            );
+            x += width;
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{:.1}" y="{}">{:.1?}</text>"#,
+            x + 6.0,
+            y + 16,
+            day_total(timing)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Formats a standalone HTML report with a chart and a table of the timings. It does not load any external assets.
+fn to_html(year: Year, timings: &[Timings]) -> String {
+    let format = |x: Option<&Stats>| x.map_or_else(|| "-".into(), |x| format!("{:.1?}", x.median));
+
+    let rows: Vec<String> = timings
+        .iter()
+        .map(|x| {
+            format!(
+                "      <tr><td>Day {}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                x.day,
+                format(x.parse.as_ref()),
+                format(x.part_1.as_ref()),
+                format(x.part_2.as_ref())
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>Advent of Code {year} benchmarks</title>
+    <style>
+      body {{ font-family: system-ui, sans-serif; margin: 2rem; color: #1f2937; }}
+      svg text {{ font-size: 12px; fill: currentColor; }}
+      table {{ border-collapse: collapse; margin-top: 2rem; }}
+      th, td {{ padding: 0.25rem 1rem; text-align: right; border-bottom: 1px solid #e5e7eb; }}
+      th:first-child, td:first-child {{ text-align: left; }}
+    </style>
+  </head>
+  <body>
+    <h1>Advent of Code {year} benchmarks</h1>
+    <p><strong>Total: {:.2}ms</strong></p>
+    {}
+    <table>
+      <tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th></tr>
+{}
+    </table>
+  </body>
+</html>
+"#,
+        total(timings).as_secs_f64() * 1000.0,
+        to_svg(timings).replace('\n', "\n    "),
+        rows.join("\n")
+    )
+}
+
+/// Writes the timings of a run of `year` to the path of `export`, in its format.
+pub fn write(year: Year, timings: &[Timings], export: &Export) -> Result<(), io::Error> {
+    let content = match export.format {
+        ExportFormat::Csv => to_csv(year, timings),
+        ExportFormat::Json => to_json(year, timings),
+        ExportFormat::Html => to_html(year, timings),
+    };
+
+    fs::write(&export.path, content)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{to_csv, to_html, to_json, ExportFormat};
+    use crate::template::readme_benchmarks::tests::get_mock_timings;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::Stats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    /// The timings of the readme tests, where day 2 parses its input once and has no second part.
+    fn get_timings() -> Vec<Timings> {
+        let mut timings = get_mock_timings();
+        let parse = Some(Stats::single(Duration::from_millis(5)));
+        timings[1] = Timings::new(day!(2023, 2), parse, timings[1].part_1, None);
+        timings
+    }
+
+    #[test]
+    fn parse_export_format() {
+        assert_eq!("html".parse::<ExportFormat>().unwrap(), ExportFormat::Html);
+        assert_eq!("xml".parse::<ExportFormat>().is_err(), true);
+    }
+
+    #[test]
+    fn export_csv() {
+        let csv = to_csv(year!(2023), &get_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(
+            lines[1],
+            "2023,1,1,10000000,10000000,0,10000000,10000000,10000000,10000000,1,0"
+        );
+        assert_eq!(lines[3].starts_with("2023,2,parse,5000000,"), true);
+    }
+
+    #[test]
+    fn export_json() {
+        let json = to_json(year!(2023), &get_timings());
+
+        assert_eq!(
+            json.starts_with(r#"{"year":2023,"total_ns":155000000,"days":[{"day":1,"parse":null,"part_1":{"samples":1,"outliers":0,"duration_ns":{"mean":10000000,"median":10000000"#),
+            true
+        );
+        assert_eq!(json.contains(r#""part_2":null},{"day":4"#), true);
+    }
+
+    #[test]
+    fn export_html() {
+        let html = to_html(year!(2023), &get_timings());
+
+        assert_eq!(html.contains("<strong>Total: 155.00ms</strong>"), true);
+        // the bar of the slowest day fills the chart, its label follows right after.
+        assert_eq!(
+            html.contains(r#"<text x="670.0" y="88">90.0ms</text>"#),
+            true
+        );
+        assert_eq!(html.contains(r#"<title>Day 02 Parse: 5.0ms</title>"#), true);
+        assert_eq!(html.contains("<link") || html.contains("<script"), false);
+    }
+}
@@ -0,0 +1,440 @@
+/// Module that keeps a history of benchmark runs in `data/benchmarks/YYYY/history.jsonl`, to detect parts that got
+/// slower.
+///
+/// Every timed release run of `all` appends one line with its timestamp, the git commit and a fingerprint of the
+/// machine it ran on. Runs can be saved under a name (e.g. `main`) to compare later runs against them.
+use std::collections::HashMap;
+use std::env::{self, consts};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::Timings;
+use crate::template::report::{ordered_object, stringify, PARSE_PART};
+use crate::template::runner::Stats;
+use crate::template::{get_data_path, ledger};
+use crate::{Day, Year};
+
+/// The default threshold in percent above which a part counts as slower.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// A benchmark run of one or more days.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    /// Unix timestamp of the run.
+    pub timestamp: u64,
+    /// The abbreviated git commit the run was built from, suffixed with `-dirty` if there were uncommitted changes.
+    pub commit: Option<String>,
+    pub machine: String,
+    /// The name the run was saved under, if any.
+    pub baseline: Option<String>,
+    /// The history only keeps the median of every part, so the parts of loaded runs hold it as a single sample.
+    pub days: Vec<Timings>,
+}
+
+impl Run {
+    /// Creates a run of the current commit on this machine.
+    #[must_use]
+    pub fn new(timings: &[Timings], baseline: Option<String>) -> Self {
+        Self {
+            timestamp: ledger::now(),
+            commit: git_commit(),
+            machine: machine_fingerprint(),
+            baseline,
+            days: timings.to_vec(),
+        }
+    }
+
+    fn get(&self, day: Day) -> Option<&Timings> {
+        self.days.iter().find(|x| x.day == day)
+    }
+
+    /// Describes the run, e.g. `abc1234 (baseline "main", 3h ago)`.
+    #[must_use]
+    pub fn describe(&self, now: u64) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown commit");
+        let age = format_age(now.saturating_sub(self.timestamp));
+
+        match &self.baseline {
+            Some(name) => format!("{commit} (baseline \"{name}\", {age})"),
+            None => format!("{commit} ({age})"),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn to_json(&self) -> String {
+        let optional =
+            |x: &Option<String>| stringify(x.clone().map_or(JsonValue::Null, JsonValue::from));
+        let nanos = |x: Option<&Stats>| {
+            stringify(x.map_or(JsonValue::Null, |x| (x.median.as_nanos() as f64).into()))
+        };
+
+        let days: Vec<String> = self
+            .days
+            .iter()
+            .map(|x| {
+                ordered_object(&[
+                    ("day", stringify(f64::from(x.day.into_inner()).into())),
+                    ("parse", nanos(x.get(PARSE_PART))),
+                    ("part_1", nanos(x.get(1))),
+                    ("part_2", nanos(x.get(2))),
+                ])
+            })
+            .collect();
+
+        ordered_object(&[
+            ("timestamp", stringify((self.timestamp as f64).into())),
+            ("commit", optional(&self.commit)),
+            ("machine", stringify(self.machine.clone().into())),
+            ("baseline", optional(&self.baseline)),
+            ("days", format!("[{}]", days.join(","))),
+        ])
+    }
+
+    fn from_json(s: &str, year: Year) -> Option<Self> {
+        let value: JsonValue = s.trim().parse().ok()?;
+        let map: &HashMap<String, JsonValue> = value.get()?;
+        let string = |key: &str| map.get(key).and_then(|x| x.get::<String>()).cloned();
+
+        let days: &Vec<JsonValue> = map.get("days")?.get()?;
+        let days = days
+            .iter()
+            .map(|x| {
+                let map: &HashMap<String, JsonValue> = x.get()?;
+                let nanos = |key: &str| {
+                    map.get(key)
+                        .and_then(|x| x.get::<f64>())
+                        .map(|x| Stats::single(Duration::from_secs_f64(x / 1_000_000_000_f64)))
+                };
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let day = Day::new_in(year, *map.get("day")?.get::<f64>()? as u8)?;
+                Some(Timings::new(
+                    day,
+                    nanos("parse"),
+                    nanos("part_1"),
+                    nanos("part_2"),
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(Self {
+            timestamp: *map.get("timestamp")?.get::<f64>()? as u64,
+            commit: string("commit"),
+            machine: string("machine")?,
+            baseline: string("baseline"),
+            days,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What to compare the timings of a run against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// Compare against the latest run saved under this name instead of the last run.
+    pub baseline: Option<String>,
+    /// The change in percent above which a part counts as slower or faster.
+    pub threshold: f64,
+}
+
+/// The change of the timing of a part between two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change<'a> {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+    /// The run the timing is compared against.
+    pub run: &'a Run,
+}
+
+impl Change<'_> {
+    /// The change in percent, positive if the part got slower.
+    #[must_use]
+    pub fn percent(&self) -> f64 {
+        let before = self.before.as_secs_f64();
+        if before == 0.0 {
+            return 0.0;
+        }
+        (self.after.as_secs_f64() - before) / before * 100.0
+    }
+}
+
+/// Compares the timings of `current` against `history`. Every day is compared against the latest run that timed it,
+/// or the latest run saved under the name of [`Comparison::baseline`].
+#[must_use]
+pub fn compare<'a>(history: &'a [Run], current: &Run, comparison: &Comparison) -> Vec<Change<'a>> {
+    let mut changes = vec![];
+
+    for timings in &current.days {
+        let previous = history
+            .iter()
+            .rev()
+            .filter(|x| comparison.baseline.is_none() || x.baseline == comparison.baseline)
+            .find_map(|run| run.get(timings.day).map(|x| (run, x)));
+
+        let Some((run, previous)) = previous else {
+            continue;
+        };
+
+        for part in [PARSE_PART, 1, 2] {
+            if let (Some(before), Some(after)) = (previous.get(part), timings.get(part)) {
+                changes.push(Change {
+                    day: timings.day,
+                    part,
+                    before: before.median,
+                    after: after.median,
+                    run,
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+/// Formats the result of a comparison, flagging parts that changed by more than the threshold.
+#[must_use]
+pub fn format_comparison(changes: &[Change], current: &Run, comparison: &Comparison) -> String {
+    if changes.is_empty() {
+        return match &comparison.baseline {
+            Some(name) => {
+                format!("No timings to compare against, no run was saved as \"{name}\" yet.")
+            }
+            None => "No timings to compare against, this is the first run.".into(),
+        };
+    };
+
+    let now = ledger::now();
+    let mut lines = vec![];
+
+    if changes.iter().any(|x| x.run.machine != current.machine) {
+        lines.push(format!(
+            "Warning: some timings were recorded on a different machine than this one ({}).",
+            current.machine
+        ));
+    }
+
+    for (i, change) in changes.iter().enumerate() {
+        // days are compared against the latest run that timed them, which is not the same run for every day.
+        if i == 0 || !std::ptr::eq(changes[i - 1].run, change.run) {
+            lines.push(format!("Compared to {}:", change.run.describe(now)));
+        }
+
+        let part = match change.part {
+            PARSE_PART => "parse".to_string(),
+            part => format!("part {part}"),
+        };
+        let percent = change.percent();
+        let flag = if percent > comparison.threshold {
+            " ✖ slower"
+        } else if percent < -comparison.threshold {
+            " ✔ faster"
+        } else {
+            ""
+        };
+
+        lines.push(format!(
+            "Day {} {part}: {:.1?} → {:.1?} ({percent:+.1}%){flag}",
+            change.day, change.before, change.after
+        ));
+    }
+
+    let slower = changes
+        .iter()
+        .filter(|x| x.percent() > comparison.threshold)
+        .count();
+    lines.push(format!(
+        "{slower} of {} parts got slower by more than {}%.",
+        changes.len(),
+        comparison.threshold
+    ));
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[must_use]
+pub fn get_path_for_history(year: Year) -> PathBuf {
+    get_data_path("benchmarks", year, "history.jsonl")
+}
+
+/// Loads the benchmark history of a year, oldest run first. Years without a file have no runs.
+pub fn load(year: Year) -> Result<Vec<Run>, io::Error> {
+    match fs::read_to_string(get_path_for_history(year)) {
+        Ok(s) => Ok(s.lines().filter_map(|x| Run::from_json(x, year)).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends a run to the benchmark history of a year.
+pub fn record(year: Year, run: &Run) -> Result<(), io::Error> {
+    let path = get_path_for_history(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", run.to_json())
+}
+
+/// The abbreviated commit of `HEAD`, suffixed with `-dirty` if tracked files have uncommitted changes.
+fn git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|x| x.status.success())
+            .map(|x| String::from_utf8_lossy(&x.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let is_dirty =
+        git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|x| !x.is_empty());
+
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/// Describes the machine, so that timings of different machines are not compared unknowingly.
+fn machine_fingerprint() -> String {
+    let host = env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .unwrap_or_else(|| "unknown".into());
+
+    let cpus = thread::available_parallelism().map_or(1, std::num::NonZero::get);
+
+    // only available on linux, other platforms are told apart by host name.
+    let cpu_model = fs::read_to_string("/proc/cpuinfo").ok().and_then(|x| {
+        x.lines()
+            .find(|x| x.starts_with("model name"))
+            .and_then(|x| x.split_once(':'))
+            .map(|(_, model)| format!(", {}", model.trim()))
+    });
+
+    format!(
+        "{host} {}-{} {cpus} cpus{}",
+        consts::OS,
+        consts::ARCH,
+        cpu_model.unwrap_or_default()
+    )
+}
+
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => "just now".into(),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..86400 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_age, Comparison, Run};
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::Stats;
+    use crate::{day, year, Day};
+    use std::time::Duration;
+
+    fn us(micros: u64) -> Option<Stats> {
+        Some(Stats::single(Duration::from_micros(micros)))
+    }
+
+    fn run(commit: &str, baseline: Option<&str>, days: &[(u8, u64, u64)]) -> Run {
+        Run {
+            timestamp: 1_700_000_000,
+            commit: Some(commit.into()),
+            machine: "host linux-x86_64 8 cpus".into(),
+            baseline: baseline.map(String::from),
+            days: days
+                .iter()
+                .map(|(day, part_1, part_2)| {
+                    let day = Day::new_in(year!(2023), *day).unwrap();
+                    Timings::new(day, None, us(*part_1), us(*part_2))
+                })
+                .collect(),
+        }
+    }
+
+    fn comparison(baseline: Option<&str>) -> Comparison {
+        Comparison {
+            baseline: baseline.map(String::from),
+            threshold: 10.0,
+        }
+    }
+
+    #[test]
+    fn roundtrip_run() {
+        let mut run = run("abc1234", Some("main"), &[(1, 10, 20), (5, 1000, 2500)]);
+        let day = &run.days[0];
+        let parse = Some(Stats::single(Duration::from_nanos(750)));
+        run.days[0] = Timings::new(day.day, parse, day.part_1, day.part_2);
+
+        assert_eq!(Run::from_json(&run.to_json(), year!(2023)), Some(run));
+    }
+
+    #[test]
+    fn compare_against_last_run() {
+        let history = [
+            run("aaa", None, &[(1, 100, 100), (2, 100, 100)]),
+            run("bbb", None, &[(1, 100, 200)]),
+        ];
+        let current = run("ccc", None, &[(1, 150, 100), (2, 105, 100)]);
+
+        let changes = compare(&history, &current, &comparison(None));
+
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[0].day, day!(2023, 1));
+        assert_eq!(changes[0].run.commit.as_deref(), Some("bbb"));
+        assert_eq!(changes[0].percent().round(), 50.0);
+        assert_eq!(changes[1].percent().round(), -50.0);
+        // day 2 was not timed in the last run, so it is compared against the run before.
+        assert_eq!(changes[2].run.commit.as_deref(), Some("aaa"));
+        assert_eq!(changes[2].percent().round(), 5.0);
+    }
+
+    #[test]
+    fn compare_against_baseline() {
+        let history = [
+            run("aaa", Some("main"), &[(1, 100, 100)]),
+            run("bbb", None, &[(1, 200, 200)]),
+        ];
+        let current = run("ccc", None, &[(1, 200, 200)]);
+
+        let changes = compare(&history, &current, &comparison(Some("main")));
+        assert_eq!(changes[0].run.commit.as_deref(), Some("aaa"));
+        assert_eq!(changes[0].percent().round(), 100.0);
+
+        assert_eq!(
+            compare(&history, &current, &comparison(Some("other"))).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn age() {
+        assert_eq!(format_age(5), "just now");
+        assert_eq!(format_age(150), "2m ago");
+        assert_eq!(format_age(7300), "2h ago");
+        assert_eq!(format_age(200_000), "2d ago");
+    }
+}
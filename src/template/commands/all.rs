@@ -1,46 +1,582 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::{io, panic, path::Path, process, thread};
 
 use crate::template::{
+    bench_export::{self, Export},
+    bench_history::{self, Comparison},
+    get_bin_name, get_path_for_bin, read_file,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::{self, Solution},
+    report::{self, Format, PartReport, Status, PARSE_PART},
+    runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySelection, Year};
+
+/// Options of the `all` and `time` commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Stop running days after the first failure.
+    pub is_fail_fast: bool,
+    /// The number of days to run concurrently. Timed runs are always sequential.
+    pub jobs: usize,
+    pub format: Format,
+    /// Compare the timings of a timed release run against the benchmark history.
+    pub compare: Option<Comparison>,
+    /// Name under which the timings of a timed release run are recorded as a baseline.
+    pub save_baseline: Option<String>,
+    /// Where to export the timings of a timed run to.
+    pub export: Option<Export>,
+}
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+impl Options {
+    /// Whether days run concurrently, see [`Options::jobs`].
+    fn is_parallel(&self) -> bool {
+        self.jobs > 1 && !self.is_timed
+    }
+}
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+/// Runs the solutions of the selected `days` via the `solutions` binary, which links all days into one process.
+/// If that binary can't be built, every day is invoked as its own binary instead, which tells apart the days that
+/// fail to build.
+pub fn handle(year: Year, days: &DaySelection, options: &Options) {
+    if options.jobs > 1 && options.is_timed {
+        eprintln!(
+            "Timed runs are sequential to avoid interference between benchmarks, ignoring --jobs."
+        );
+    }
+
+    if (options.compare.is_some() || options.save_baseline.is_some())
+        && !(options.is_timed && options.is_release)
+    {
+        eprintln!("Only timed release runs are recorded in the benchmark history, ignoring --compare and --save-baseline.");
+    }
+
+    if options.export.is_some() && !options.is_timed {
+        eprintln!("Only timed runs can be exported, ignoring --export.");
+    }
+
+    if registry::build(options.is_release).is_ok_and(|x| x.success()) {
+        let mut args = vec![
+            "all".to_string(),
+            "--year".into(),
+            year.to_string(),
+            "--days".into(),
+            days.to_string(),
+            "--jobs".into(),
+            options.jobs.to_string(),
+        ];
+
+        if options.is_release {
+            args.push("--release".into());
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        if options.is_timed {
+            args.push("--time".into());
+        }
+
+        if options.is_fail_fast {
+            args.push("--fail-fast".into());
+        }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if let Some(comparison) = &options.compare {
+            args.push("--compare".into());
+            args.push("--threshold".into());
+            args.push(comparison.threshold.to_string());
 
-        if output.is_empty() {
-            println!("Not solved.");
+            if let Some(baseline) = &comparison.baseline {
+                args.push("--baseline".into());
+                args.push(baseline.clone());
+            }
+        }
+
+        if let Some(name) = &options.save_baseline {
+            args.push("--save-baseline".into());
+            args.push(name.clone());
+        }
+
+        if let Some(export) = &options.export {
+            args.push("--export-format".into());
+            args.push(export.format.to_string());
+            args.push("--export-path".into());
+            args.push(export.path.to_string_lossy().into());
+        }
+
+        args.push("--format".into());
+        args.push(options.format.to_string());
+
+        match registry::run(&args, options.is_release) {
+            Ok(status) if status.success() => {}
+            Ok(status) => process::exit(status.code().unwrap_or(1)),
+            Err(e) => {
+                eprintln!("Failed to run solutions: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    eprintln!("Could not build the solutions binary, running every day separately instead.");
+
+    // in parallel runs, output is buffered and printed in order once the day is done.
+    let is_buffered = options.is_parallel();
+
+    run_days(year, days, options, |day| {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return DayRun::new(day, DayStatus::NotScaffolded);
+        }
+
+        let bin_name = get_bin_name(year, day);
+
+        match child_commands::build_solution(None, &bin_name, options.is_release) {
+            Ok(true) => {}
+            Ok(false) => return DayRun::new(day, DayStatus::BuildFailed),
+            Err(e) => {
+                let message = format!("Failed to build solution: {e:?}");
+                return DayRun::with_message(day, DayStatus::BuildFailed, message, is_buffered);
+            }
+        }
+
+        if let Err(e) = read_file("inputs", year, day) {
+            let message = format!("Skipped: {e}");
+            return DayRun::with_message(day, DayStatus::MissingInput, message, is_buffered);
+        }
+
+        let output = match child_commands::run_solution(None, &bin_name, options, is_buffered) {
+            Ok(output) => output,
+            Err(e) => {
+                let message = format!("Failed to run solution: {e:?}");
+                return DayRun::with_message(day, DayStatus::RuntimeFailure, message, is_buffered);
+            }
+        };
+
+        let reports = child_commands::collect_reports(&output.lines);
+        let timings = child_commands::collect_timings(&output.lines, day);
+
+        let run = DayRun {
+            output: if is_buffered { output.lines } else { vec![] },
+            ..DayRun::from_reports(day, &reports, timings)
+        };
+
+        if output.success {
+            run
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            DayRun {
+                status: DayStatus::RuntimeFailure,
+                ..run
+            }
         }
     });
+}
+
+/// Runs the registered solutions of the selected `days` in-process. This is the entry point of the `solutions` binary.
+pub fn run(solutions: &[Solution], year: Year, days: &DaySelection, options: &Options) {
+    // in parallel runs, parts don't print their results and the reports are printed in order instead.
+    let is_buffered = options.is_parallel();
+    runner::set_quiet(is_buffered);
+
+    run_days(year, days, options, |day| {
+        let Some(solution) = solutions.iter().find(|x| x.year == year && x.day == day) else {
+            return DayRun::new(day, DayStatus::NotScaffolded);
+        };
+
+        let input = match read_file("inputs", year, day) {
+            Ok(input) => input,
+            Err(e) => {
+                let message = format!("Skipped: {e}");
+                return DayRun::with_message(day, DayStatus::MissingInput, message, is_buffered);
+            }
+        };
+
+        // parts catch their own panics, so this only catches panics while parsing.
+        match panic::catch_unwind(|| (solution.run)(&input)) {
+            Ok(reports) => DayRun {
+                output: if is_buffered {
+                    reports.iter().map(PartReport::to_json).collect()
+                } else {
+                    vec![]
+                },
+                ..DayRun::from_reports(day, &reports, timings_from_reports(&reports, day))
+            },
+            Err(_) => DayRun::new(day, DayStatus::RuntimeFailure),
+        }
+    });
+}
+
+/// The outcome of running the solution of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayStatus {
+    NotScaffolded,
+    MissingInput,
+    BuildFailed,
+    /// The solution exited with an error, or a part returned an error or panicked.
+    RuntimeFailure,
+    /// The solution ran, but not every part has an answer yet.
+    Unsolved,
+    Solved,
+}
+
+impl DayStatus {
+    /// Derives the status of a day that ran from the reports of its parts.
+    fn from_reports(reports: &[PartReport]) -> Self {
+        let parts = || reports.iter().filter(|x| x.part != PARSE_PART);
 
-    if is_timed {
+        if parts().any(|x| matches!(x.status, Status::Failed | Status::Panicked)) {
+            DayStatus::RuntimeFailure
+        } else if parts().count() > 0 && parts().all(|x| x.status == Status::Solved) {
+            DayStatus::Solved
+        } else {
+            DayStatus::Unsolved
+        }
+    }
+
+    /// Whether this status should fail the run.
+    #[must_use]
+    pub fn is_failure(self) -> bool {
+        matches!(self, DayStatus::BuildFailed | DayStatus::RuntimeFailure)
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DayStatus::NotScaffolded => "not scaffolded",
+            DayStatus::MissingInput => "no input",
+            DayStatus::BuildFailed => "build failed",
+            DayStatus::RuntimeFailure => "runtime failure",
+            DayStatus::Unsolved => "unsolved",
+            DayStatus::Solved => "solved",
+        }
+    }
+}
+
+/// The results of a day.
+struct DayRun {
+    day: Day,
+    status: DayStatus,
+    /// The timings of the day, if its solution ran.
+    timings: Option<Timings>,
+    /// Reports of the parts that returned an error or panicked.
+    failed: Vec<PartReport>,
+    /// The buffered output of the solution, if it ran in parallel with other days.
+    output: Vec<String>,
+    /// Buffered messages for stderr, e.g. why the day was skipped, if it ran in parallel with other days.
+    messages: Vec<String>,
+}
+
+impl DayRun {
+    /// Creates the results of a day whose solution did not report any parts.
+    fn new(day: Day, status: DayStatus) -> Self {
+        Self {
+            day,
+            status,
+            timings: None,
+            failed: vec![],
+            output: vec![],
+            messages: vec![],
+        }
+    }
+
+    /// Creates the results of a day whose solution did not run, with a message that explains why.
+    /// The message is printed to stderr right away, unless the output of days is buffered.
+    fn with_message(day: Day, status: DayStatus, message: String, is_buffered: bool) -> Self {
+        if !is_buffered {
+            eprintln!("{message}");
+            return Self::new(day, status);
+        }
+
+        Self {
+            messages: vec![message],
+            ..Self::new(day, status)
+        }
+    }
+
+    fn from_reports(day: Day, reports: &[PartReport], timings: Timings) -> Self {
+        Self {
+            day,
+            status: DayStatus::from_reports(reports),
+            timings: Some(timings),
+            failed: failures_from_reports(reports),
+            output: vec![],
+            messages: vec![],
+        }
+    }
+}
+
+/// Prints the results of every selected day as returned by `run_day`, followed by a summary, and updates the README
+/// benchmarks if timed and every day was selected.
+///
+/// `run_day` is expected to print the results of the parts of a day, unless days run in parallel, in which case it
+/// buffers them in [`DayRun::output`]. With `is_fail_fast`, no more days are run after the first failure.
+///
+/// Exits with a non-zero status if any day failed to build or run.
+fn run_days(
+    year: Year,
+    days: &DaySelection,
+    options: &Options,
+    run_day: impl Fn(Day) -> DayRun + Sync,
+) {
+    let format = options.format;
+    let selected: Vec<Day> = days.iter().collect();
+    let mut runs: Vec<DayRun> = vec![];
+
+    // records the results of a day, returns whether to continue with the next day.
+    let mut record = |run: DayRun| {
+        print_status(&run, format);
+
+        let is_stopping = options.is_fail_fast && run.status.is_failure();
+        runs.push(run);
+
+        if is_stopping && format == Format::Human {
+            println!("\nStopping after the first failure (--fail-fast).");
+        }
+
+        !is_stopping
+    };
+
+    if options.is_parallel() {
+        run_parallel(&selected, options.jobs, &run_day, |i, run| {
+            print_header(i, run.day, format);
+            for message in &run.messages {
+                eprintln!("{message}");
+            }
+            for line in &run.output {
+                child_commands::print_line(line, format);
+            }
+            record(run)
+        });
+    } else {
+        for (i, day) in days.iter().enumerate() {
+            // the header is printed first, so the results appear below it as they arrive.
+            print_header(i, day, format);
+            if !record(run_day(day)) {
+                break;
+            }
+        }
+    }
+
+    let failed: Vec<PartReport> = runs.iter().flat_map(|x| x.failed.clone()).collect();
+
+    if format == Format::Human {
+        print_failures(&failed, Status::Failed, "Failed");
+        print_failures(&failed, Status::Panicked, "Panicked");
+        println!();
+    }
+
+    report::info(format, &format_summary(&runs));
+
+    let has_failures = runs.iter().any(|x| x.status.is_failure());
+
+    if options.is_timed {
+        let timings: Vec<Timings> = runs.into_iter().filter_map(|x| x.timings).collect();
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if format == Format::Human {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+        if let Some(export) = &options.export {
+            let path = export.path.display();
+            match bench_export::write(year, &timings, export) {
+                Ok(()) => report::info(format, &format!("Exported benchmarks to \"{path}\".")),
+                Err(e) => eprintln!("Failed to export benchmarks to \"{path}\": {e}"),
+            }
+        }
+
+        if options.is_release {
+            update_history(year, &timings, options);
+        }
+
+        if options.is_release && !days.is_all(year) {
+            // a partial table would drop the benchmarks of the days that were not run.
+            eprintln!("Not updating README benchmarks, as only some days were selected.");
+        } else if options.is_release {
+            match readme_benchmarks::update(year, timings, total_millis) {
+                Ok(()) => report::info(format, "Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
     }
+
+    if has_failures {
+        process::exit(1);
+    }
+}
+
+/// Records the timings of a run in the benchmark history, after comparing them against it if requested.
+fn update_history(year: Year, timings: &[Timings], options: &Options) {
+    let run = bench_history::Run::new(timings, options.save_baseline.clone());
+
+    if let Some(comparison) = &options.compare {
+        match bench_history::load(year) {
+            Ok(history) => {
+                let changes = bench_history::compare(&history, &run, comparison);
+                let text = bench_history::format_comparison(&changes, &run, comparison);
+                if options.format == Format::Human {
+                    println!();
+                }
+                report::info(options.format, &text);
+            }
+            Err(e) => eprintln!("Failed to read benchmark history: {e}"),
+        }
+    }
+
+    if let Err(e) = bench_history::record(year, &run) {
+        eprintln!("Failed to record benchmark history: {e}");
+    }
+}
+
+fn print_header(i: usize, day: Day, format: Format) {
+    if format == Format::Human {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    }
+}
+
+/// Prints the status of a day, unless its results were printed by the solution itself.
+fn print_status(run: &DayRun, format: Format) {
+    if format == Format::Human {
+        match run.status {
+            DayStatus::NotScaffolded => println!("Not scaffolded."),
+            DayStatus::MissingInput => println!("No input."),
+            DayStatus::BuildFailed => println!("Build failed."),
+            // failed parts were printed with their error already.
+            DayStatus::RuntimeFailure if run.failed.is_empty() => println!("Failed."),
+            _ => {}
+        }
+    }
+}
+
+/// Runs `run_day` for every day on `jobs` threads, passing the results to `on_result` in the order of `days` as
+/// soon as every earlier day is done. Once `on_result` returns `false`, no more days are started.
+fn run_parallel(
+    days: &[Day],
+    jobs: usize,
+    run_day: &(impl Fn(Day) -> DayRun + Sync),
+    mut on_result: impl FnMut(usize, DayRun) -> bool,
+) {
+    let next = &AtomicUsize::new(0);
+    let is_stopped = &AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                while !is_stopped.load(Ordering::Relaxed) {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(i) else {
+                        break;
+                    };
+
+                    if sender.send((i, run_day(*day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // only the threads hold senders now, so receiving ends once they are all done.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+
+        for (i, run) in receiver {
+            pending.insert(i, run);
+
+            while let Some(run) = pending.remove(&expected) {
+                if !on_result(expected, run) {
+                    is_stopped.store(true, Ordering::Relaxed);
+                    return;
+                }
+                expected += 1;
+            }
+        }
+    });
+}
+
+/// Formats a table of the status of every scaffolded day, followed by the number of days per status.
+fn format_summary(runs: &[DayRun]) -> String {
+    let mut lines = vec![format!("{ANSI_BOLD}Summary{ANSI_RESET}"), "-------".into()];
+
+    for run in runs.iter().filter(|x| x.status != DayStatus::NotScaffolded) {
+        let symbol = match run.status {
+            DayStatus::Solved => "✔",
+            status if status.is_failure() => "✖",
+            _ => "?",
+        };
+        lines.push(format!("Day {}  {symbol} {}", run.day, run.status.as_str()));
+    }
+
+    let count =
+        |statuses: &[DayStatus]| runs.iter().filter(|x| statuses.contains(&x.status)).count();
+
+    lines.push(format!(
+        "{ANSI_BOLD}Solved:{ANSI_RESET} {}, {ANSI_BOLD}unsolved:{ANSI_RESET} {}, {ANSI_BOLD}failed:{ANSI_RESET} {}, {ANSI_BOLD}skipped:{ANSI_RESET} {}",
+        count(&[DayStatus::Solved]),
+        count(&[DayStatus::Unsolved]),
+        count(&[DayStatus::BuildFailed, DayStatus::RuntimeFailure]),
+        count(&[DayStatus::MissingInput, DayStatus::NotScaffolded]),
+    ));
+
+    lines.join("\n")
+}
+
+/// Collects the timings of a day from the reports of its parts.
+fn timings_from_reports(reports: &[PartReport], day: Day) -> Timings {
+    let stats_for = |part: u8| {
+        reports
+            .iter()
+            .find(|x| x.part == part)
+            .and_then(|x| x.stats)
+    };
+
+    Timings::new(day, stats_for(PARSE_PART), stats_for(1), stats_for(2))
+}
+
+/// Lists the parts of `failed` that have the given `status`, with their error.
+fn print_failures(failed: &[PartReport], status: Status, title: &str) {
+    let reports: Vec<&PartReport> = failed.iter().filter(|x| x.status == status).collect();
+
+    if reports.is_empty() {
+        return;
+    }
+
+    let noun = if reports.len() == 1 { "part" } else { "parts" };
+    println!("\n{ANSI_BOLD}{title}:{ANSI_RESET} {} {noun}", reports.len());
+
+    for report in reports {
+        let part = match report.part {
+            PARSE_PART => "parse".into(),
+            part => format!("part {part}"),
+        };
+        println!(
+            "Day {}, {part}: {}",
+            report.day,
+            report.error.as_deref().unwrap_or_default()
+        );
+    }
+}
+
+/// Collects the reports of the parts that returned an error or panicked.
+fn failures_from_reports(reports: &[PartReport]) -> Vec<PartReport> {
+    reports
+        .iter()
+        .filter(|x| matches!(x.status, Status::Failed | Status::Panicked))
+        .cloned()
+        .collect()
 }
 
 #[derive(Debug)]
@@ -56,47 +592,94 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
+pub(crate) mod child_commands {
+    use super::{Error, Options};
+    use crate::template::cargo_command;
+    use crate::template::report::{self, Format, PartReport};
+    use crate::template::runner::{print_report, Stats};
     use crate::Day;
     use std::{
+        env,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// The output of a solution bin.
+    pub struct Output {
+        pub lines: Vec<String>,
+        /// Whether the bin exited successfully.
+        pub success: bool,
+    }
+
+    /// Creates a cargo command that runs in the checkout at `dir`, or the current one.
+    fn cargo(dir: Option<&Path>) -> Result<Command, Error> {
+        let mut cmd = cargo_command();
+
+        if let Some(dir) = dir {
+            // other checkouts share a target dir, so their dependencies are only built once.
+            let target_dir = env::current_dir()?.join("target").join("checkouts");
+            cmd.current_dir(dir).env("CARGO_TARGET_DIR", target_dir);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        Ok(cmd)
+    }
+
+    /// Builds the solution bin with the given name, returns whether the build succeeded.
+    /// Compiler errors are forwarded to stderr.
+    pub fn build_solution(
+        dir: Option<&Path>,
+        bin_name: &str,
+        is_release: bool,
+    ) -> Result<bool, Error> {
+        let mut args = vec!["build", "--quiet", "--bin", bin_name];
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        let status = cargo(dir)?
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        Ok(status.success())
+    }
+
+    /// Run the solution bin with the given name in the checkout at `dir`, or the current one.
+    /// Children report their results as json, which is rendered in the requested format as it arrives, unless
+    /// `is_buffered` is set.
+    pub fn run_solution(
+        dir: Option<&Path>,
+        bin_name: &str,
+        options: &Options,
+        is_buffered: bool,
+    ) -> Result<Output, Error> {
+        let mut args = vec!["run", "--quiet", "--bin", bin_name];
+
+        if options.is_release {
+            args.push("--release");
+        }
+
+        args.push("--");
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        args.push("--format");
+        args.push("json");
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = cargo(dir)?
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -115,19 +698,56 @@ mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+
+            if !is_buffered {
+                print_line(&line, options.format);
+            }
+
             output.push(line);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(Output {
+            lines: output,
+            success: status.success(),
+        })
+    }
+
+    /// Prints a line of a solution's output, rendering reports in the given `format`.
+    pub fn print_line(line: &str, format: Format) {
+        match PartReport::from_json(line) {
+            Some(report) => print_report(&report, format),
+            // forward anything else the solution prints, e.g. debug output.
+            None => report::info(format, line),
+        }
+    }
+
+    /// Collects timings from the json reports in a solution's output.
+    /// Falls back to parsing the human-readable output for binaries that do not emit reports.
+    pub fn collect_timings(output: &[String], day: Day) -> super::Timings {
+        let reports = collect_reports(output);
+
+        if reports.is_empty() {
+            parse_exec_time(output, day)
+        } else {
+            super::timings_from_reports(&reports, day)
+        }
+    }
+
+    /// Collects the json reports in a solution's output.
+    pub fn collect_reports(output: &[String]) -> Vec<PartReport> {
+        output
+            .iter()
+            .filter_map(|line| PartReport::from_json(line))
+            .collect()
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
@@ -140,19 +760,19 @@ mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some((stats, nanos)) = parse_stats(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, stats, nanos))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, stats, nanos)| {
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1 = Some(stats);
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(stats);
                 }
 
                 timings.total_nanos += nanos;
@@ -165,25 +785,55 @@ mod child_commands {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    /// Parses a formatted [`Duration`] into nanoseconds.
+    fn parse_time(str_timing: &str) -> Option<f64> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
+        match str_timing.trim() {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        }
+    }
+
+    fn nanos_to_duration(nanos: f64) -> Duration {
+        Duration::from_secs_f64(nanos / 1_000_000_000_f64)
+    }
+
+    /// Parses a line such as `Part 1: 42 (74.1ns @ 100 samples) [σ 1.2ns, min 70.0ns, ...]`.
+    /// The bracketed details are optional, missing values default to the median.
+    fn parse_stats(line: &str) -> Option<(Stats, f64)> {
+        let (head, details) = line.split_once(" samples)")?;
+        let (str_timing, str_samples) = head.split('(').next_back()?.split_once('@')?;
+
+        let nanos = parse_time(str_timing)?;
+        let mut stats = Stats::single(nanos_to_duration(nanos));
+        stats.samples = str_samples.trim().parse().ok()?;
+
+        if let Some(details) = details
+            .trim()
+            .strip_prefix('[')
+            .and_then(|x| x.strip_suffix(']'))
+        {
+            for (key, value) in details.split(", ").filter_map(|x| x.split_once(' ')) {
+                if key == "outliers" {
+                    stats.outliers = value.parse().ok()?;
+                    continue;
+                }
 
-        Some((str_timing, parsed_timing))
+                let value = nanos_to_duration(parse_time(value)?);
+                match key {
+                    "σ" => stats.std_dev = value,
+                    "min" => stats.min = value,
+                    "p5" => stats.p5 = value,
+                    "p95" => stats.p95 = value,
+                    "max" => stats.max = value,
+                    _ => {}
+                }
+            }
+        }
+
+        Some((stats, nanos))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -202,9 +852,48 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{collect_timings, parse_exec_time};
+        use std::time::Duration;
 
-        use crate::day;
+        use crate::template::report::PartReport;
+        use crate::template::runner::Stats;
+        use crate::{day, year};
+
+        #[test]
+        fn test_collect_reports() {
+            let report = |part, answer: Option<&str>, millis| {
+                PartReport::new(
+                    year!(2023),
+                    day!(2023, 3),
+                    part,
+                    answer.map(String::from),
+                    Stats::single(Duration::from_millis(millis)),
+                )
+                .to_json()
+            };
+
+            let res = collect_timings(
+                &[
+                    "debug output (1s @ 10 samples)".into(),
+                    report(1, Some("Part 2: 1 (1s @ 10 samples)"), 2),
+                    report(2, None, 3),
+                ],
+                day!(2023, 3),
+            );
+            assert_approx_eq!(res.total_nanos, 2000000_f64);
+            assert_eq!(res.part_1.unwrap().median, Duration::from_millis(2));
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn test_collect_legacy_output() {
+            let res = collect_timings(
+                &["Part 1: 0 (74.13ns @ 100000 samples)".into(), "".into()],
+                day!(2023, 1),
+            );
+            assert_approx_eq!(res.total_nanos, 74.13_f64);
+            assert_eq!(res.part_1.unwrap().samples, 100000);
+        }
 
         #[test]
         fn test_well_formed() {
@@ -214,11 +903,13 @@ mod child_commands {
                     "Part 2: 10 (74.13ms @ 99999 samples)".into(),
                     "".into(),
                 ],
-                day!(1),
+                day!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap().median, Duration::from_nanos(74));
+            assert_eq!(res.part_1.unwrap().samples, 100000);
+            assert_eq!(res.part_2.unwrap().median, Duration::from_micros(74130));
+            assert_eq!(res.part_2.unwrap().samples, 99999);
         }
 
         #[test]
@@ -229,11 +920,31 @@ mod child_commands {
                     "Part 2: 10s (100ms @ 1 samples)".into(),
                     "".into(),
                 ],
-                day!(1),
+                day!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().median, Duration::from_secs(2));
+            assert_eq!(res.part_2.unwrap().median, Duration::from_millis(100));
+        }
+
+        #[test]
+        fn test_detailed_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (1.5µs @ 400 samples) [σ 20.0ns, min 1.4µs, p5 1.4µs, p95 1.6µs, max 1.7µs, outliers 3]".into(),
+                    "".into(),
+                ],
+                day!(2023, 1),
+            );
+            let stats = res.part_1.unwrap();
+            assert_approx_eq!(res.total_nanos, 1500_f64);
+            assert_eq!(stats.samples, 400);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(stats.std_dev, Duration::from_nanos(20));
+            assert_eq!(stats.min, Duration::from_nanos(1400));
+            assert_eq!(stats.p95, Duration::from_nanos(1600));
+            assert_eq!(stats.max, Duration::from_nanos(1700));
+            assert_eq!(res.part_2.is_none(), true);
         }
 
         #[test]
@@ -244,7 +955,7 @@ mod child_commands {
                     "Part 2: ✖        ".into(),
                     "".into(),
                 ],
-                day!(1),
+                day!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
@@ -252,3 +963,50 @@ mod child_commands {
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DayStatus;
+    use std::time::Duration;
+
+    use crate::template::report::PartReport;
+    use crate::template::runner::Stats;
+    use crate::{day, year};
+
+    fn report(part: u8, answer: Option<&str>) -> PartReport {
+        PartReport::new(
+            year!(2023),
+            day!(2023, 1),
+            part,
+            answer.map(String::from),
+            Stats::single(Duration::from_millis(1)),
+        )
+    }
+
+    #[test]
+    fn day_status_from_reports() {
+        let parse = PartReport::parse(year!(2023), day!(2023, 1), Stats::single(Duration::ZERO));
+        let failed = PartReport::failed(year!(2023), day!(2023, 1), 2, "error".into());
+        let panicked = PartReport::panicked(year!(2023), day!(2023, 1), 1, "panic".into());
+
+        assert_eq!(
+            DayStatus::from_reports(&[parse.clone(), report(1, Some("1")), report(2, Some("2"))]),
+            DayStatus::Solved
+        );
+        assert_eq!(
+            DayStatus::from_reports(&[report(1, Some("1")), report(2, None)]),
+            DayStatus::Unsolved
+        );
+        assert_eq!(
+            DayStatus::from_reports(&[report(1, Some("1")), failed]),
+            DayStatus::RuntimeFailure
+        );
+        assert_eq!(
+            DayStatus::from_reports(&[panicked, report(2, Some("2"))]),
+            DayStatus::RuntimeFailure
+        );
+        assert_eq!(DayStatus::from_reports(&[parse]), DayStatus::Unsolved);
+        assert_eq!(DayStatus::RuntimeFailure.is_failure(), true);
+        assert_eq!(DayStatus::MissingInput.is_failure(), false);
+    }
+}
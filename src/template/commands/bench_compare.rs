@@ -0,0 +1,320 @@
+/// Compares the timings of the current checkout against another git revision.
+///
+/// The revision is checked out into a temporary git worktree, where the same days are built and timed with the same
+/// settings and inputs as the current checkout. Days are timed alternately in both checkouts, so that changes of the
+/// machine's load affect both sides alike.
+///
+/// Revisions before year-scoped binaries keep their solutions in `src/bin/DD.rs`, these are timed as the days of the
+/// selected year. They print their timings as text, which is read with the parser of `all`.
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::template::commands::all::{child_commands, Options};
+use crate::template::readme_benchmarks::Timings;
+use crate::template::report::{Format, PARSE_PART};
+use crate::template::runner::Stats;
+use crate::template::{
+    get_bin_name, get_data_path, get_path_for_bin, read_file, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, DaySelection, Year};
+
+/// A temporary git worktree, which is removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(commit: &str) -> io::Result<Self> {
+        let path = env::temp_dir().join(format!("aoc-bench-compare-{}", process::id()));
+        let path_str = path.to_string_lossy();
+
+        let status = Command::new("git")
+            .args(["worktree", "add", "--detach", "--quiet", &path_str, commit])
+            .status()?;
+
+        if !status.success() {
+            return Err(io::Error::other("git worktree add failed"));
+        }
+
+        Ok(Self { path })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let path = self.path.to_string_lossy();
+        let status = Command::new("git")
+            .args(["worktree", "remove", "--force", &path])
+            .status();
+
+        if !status.is_ok_and(|x| x.success()) {
+            eprintln!("Failed to remove the worktree at \"{path}\", run `git worktree prune` to clean it up.");
+        }
+    }
+}
+
+/// The solution of a day in a checkout.
+struct DayBin {
+    name: String,
+    /// The path of the day's input, relative to the checkout.
+    input_path: PathBuf,
+}
+
+impl DayBin {
+    /// Finds the bin of a day in the checkout at `dir`, either year-scoped or in the layout that predates years.
+    fn find(dir: &Path, year: Year, day: Day) -> Option<Self> {
+        if dir.join(get_path_for_bin(year, day)).exists() {
+            return Some(Self {
+                name: get_bin_name(year, day),
+                input_path: get_data_path("inputs", year, &format!("{day}.txt")),
+            });
+        }
+
+        dir.join(format!("src/bin/{day}.rs"))
+            .exists()
+            .then(|| Self {
+                name: day.to_string(),
+                input_path: PathBuf::from("data")
+                    .join("inputs")
+                    .join(format!("{day}.txt")),
+            })
+    }
+}
+
+/// Resolves a revision to the short hash of its commit.
+fn resolve(rev: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "--verify", "--quiet"])
+        .arg(format!("{rev}^{{commit}}"))
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Times the selected `days` at `rev` and in the current checkout, and prints a table of the speedups.
+pub fn handle(year: Year, rev: &str, days: &DaySelection) {
+    let Some(commit) = resolve(rev) else {
+        eprintln!("Unknown revision: {rev}");
+        process::exit(1);
+    };
+
+    let worktree = match Worktree::add(&commit) {
+        Ok(worktree) => worktree,
+        Err(e) => {
+            eprintln!("Failed to check out {rev}: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Comparing {rev} ({commit}) with the working tree.");
+
+    let mut rows = vec![];
+
+    for day in days.iter() {
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            continue;
+        }
+
+        let Some(bin) = DayBin::find(&worktree.path, year, day) else {
+            println!("Day {day}: skipped, it does not exist at {rev}.");
+            continue;
+        };
+
+        match time_day(&worktree.path, &bin, year, day) {
+            Ok(row) => rows.push(row),
+            Err(e) => println!("Day {day}: skipped, {e}"),
+        }
+    }
+
+    // remove the worktree before exiting.
+    drop(worktree);
+
+    if rows.is_empty() {
+        eprintln!("No days to compare.");
+        process::exit(1);
+    }
+
+    println!("\n{}", format_table(&commit, &rows));
+}
+
+/// Times a day with its `bin` at the revision checked out in `dir` and in the current checkout.
+fn time_day(dir: &Path, bin: &DayBin, year: Year, day: Day) -> Result<(Timings, Timings), String> {
+    // both checkouts are timed against the input of the current one.
+    let input = read_file("inputs", year, day).map_err(|e| e.to_string())?;
+    let input_path = dir.join(&bin.input_path);
+    input_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&input_path, input))
+        .map_err(|e| format!("could not copy the input: {e}"))?;
+
+    println!("Day {day}: timing...");
+
+    let bin_name = get_bin_name(year, day);
+    let checkouts = [
+        (Some(dir), bin.name.as_str(), "the revision"),
+        (None, bin_name.as_str(), "the working tree"),
+    ];
+
+    for (dir, bin_name, label) in checkouts {
+        if !child_commands::build_solution(dir, bin_name, true).is_ok_and(|x| x) {
+            return Err(format!("it does not build in {label}."));
+        }
+    }
+
+    let options = Options {
+        is_release: true,
+        is_timed: true,
+        is_fail_fast: false,
+        jobs: 1,
+        format: Format::Json,
+        compare: None,
+        save_baseline: None,
+        export: None,
+    };
+
+    let time = |dir: Option<&Path>, bin_name: &str| {
+        child_commands::run_solution(dir, bin_name, &options, true)
+            .map(|output| child_commands::collect_timings(&output.lines, day))
+            .map_err(|e| format!("it could not be run: {e:?}"))
+    };
+
+    let (before, after) = (time(Some(dir), &bin.name)?, time(None, &bin_name)?);
+
+    if [PARSE_PART, 1, 2].iter().all(|x| before.get(*x).is_none()) {
+        return Err("no timings could be read from the revision.".into());
+    }
+
+    Ok((before, after))
+}
+
+/// Formats a table of the timings of each part before and after, and the speedup between them.
+/// The total only includes parts that were timed in both checkouts.
+fn format_table(commit: &str, rows: &[(Timings, Timings)]) -> String {
+    let format = |x: Option<&Stats>| x.map_or_else(|| "-".into(), |x| format!("{:.1?}", x.median));
+    let speedup = |before: Duration, after: Duration| {
+        if after.is_zero() {
+            "-".into()
+        } else {
+            format!("{:.2}×", before.as_secs_f64() / after.as_secs_f64())
+        }
+    };
+
+    let mut table = vec![[
+        "Day".to_string(),
+        "Part".into(),
+        commit.into(),
+        "working tree".into(),
+        "Speedup".into(),
+    ]];
+    let (mut total_before, mut total_after) = (Duration::ZERO, Duration::ZERO);
+
+    for (before, after) in rows {
+        for part in [PARSE_PART, 1, 2] {
+            let (stats_before, stats_after) = (before.get(part), after.get(part));
+
+            let change = match (stats_before, stats_after) {
+                (None, None) => continue,
+                (Some(x), Some(y)) => {
+                    total_before += x.median;
+                    total_after += y.median;
+                    speedup(x.median, y.median)
+                }
+                _ => "-".into(),
+            };
+
+            table.push([
+                before.day.to_string(),
+                match part {
+                    PARSE_PART => "parse".into(),
+                    part => part.to_string(),
+                },
+                format(stats_before),
+                format(stats_after),
+                change,
+            ]);
+        }
+    }
+
+    table.push([
+        "Total".into(),
+        String::new(),
+        format!("{total_before:.1?}"),
+        format!("{total_after:.1?}"),
+        speedup(total_before, total_after),
+    ]);
+
+    let widths: Vec<usize> = (0..5)
+        .map(|i| {
+            table
+                .iter()
+                .map(|x| x[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let lines: Vec<String> = table
+        .iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (cell, width))| match i {
+                    0 | 1 => format!("{cell:<width$}"),
+                    _ => format!("{cell:>width$}"),
+                })
+                .collect();
+            cells.join("  ")
+        })
+        .collect();
+
+    format!(
+        "{ANSI_BOLD}{}{ANSI_RESET}\n{}",
+        lines[0],
+        lines[1..].join("\n")
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_table;
+    use std::time::Duration;
+
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::Stats;
+
+    fn timings(part_1: u64, part_2: Option<u64>) -> Timings {
+        Timings {
+            day: day!(2023, 6),
+            parse: None,
+            part_1: Some(Stats::single(Duration::from_micros(part_1))),
+            part_2: part_2.map(|x| Stats::single(Duration::from_micros(x))),
+            total_nanos: 0_f64,
+        }
+    }
+
+    #[test]
+    fn speedup_table() {
+        let table = format_table("abc1234", &[(timings(300, Some(50)), timings(100, None))]);
+        let lines: Vec<&str> = table.lines().skip(1).collect();
+
+        assert_eq!(
+            lines,
+            [
+                "06     1     300.0µs       100.0µs    3.00×",
+                "06     2      50.0µs             -        -",
+                "Total        300.0µs       100.0µs    3.00×",
+            ]
+        );
+    }
+}
@@ -1,14 +1,17 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::{aoc_cli, aoc_client};
+use crate::{Day, Year};
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(year: Year, day: Day) {
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        if let Err(e) = aoc_client::download(year, day) {
+            eprintln!("failed to download puzzle: {e}");
+            process::exit(1);
+        }
+        return;
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = aoc_cli::download(year, day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
@@ -0,0 +1,98 @@
+use std::{fs, path::Path, process};
+
+use crate::template::answers;
+use crate::template::examples::{self, get_path_for_example, get_path_for_example_answers};
+use crate::template::{get_bin_name, get_data_path};
+use crate::{Day, Year};
+
+pub fn handle(year: Year, day: Day, force: bool) {
+    let puzzle_path = get_data_path("puzzles", year, &format!("{day}.md"));
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{}\". Run `cargo download {} --year {year}` first.",
+            puzzle_path.display(),
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    let examples = examples::extract(&puzzle);
+
+    let part_1 = examples.part_1.input.as_deref();
+    let part_2 = examples
+        .part_2
+        .input
+        .as_deref()
+        .filter(|input| Some(*input) != part_1);
+
+    if part_1.is_none() {
+        eprintln!("No example found in \"{}\".", puzzle_path.display());
+    }
+
+    for (part, input) in [(1, part_1), (2, part_2)] {
+        if let Some(input) = input {
+            write_example(&get_path_for_example(year, day, part), input, force);
+        }
+    }
+
+    let answers_path = get_path_for_example_answers(year, day);
+    let mut answers = examples::load_answers(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read example answers: {e}");
+        process::exit(1);
+    });
+
+    for (part, example) in [(1, &examples.part_1), (2, &examples.part_2)] {
+        let Some(answer) = example.answer.as_deref() else {
+            continue;
+        };
+
+        match answers.get(part) {
+            Some(existing) if existing != answer && !force => println!(
+                "Skipping detected answer {answer} for part {part}, \"{}\" already records {existing}.",
+                answers_path.display()
+            ),
+            _ => {
+                answers.set(part, answer);
+                println!("Detected example answer for part {part}: {answer}");
+            }
+        }
+    }
+
+    if let Err(e) = answers::write(&answers_path, &answers) {
+        eprintln!("Failed to write example answers: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Review the extracted examples, then run `cargo test --bin {}` to check your solution against them.",
+        get_bin_name(year, day)
+    );
+}
+
+/// Writes an example file unless it already holds a different, non-empty example.
+fn write_example(path: &Path, input: &str, force: bool) {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+
+    if !force && !existing.trim().is_empty() && existing != input {
+        println!(
+            "Skipping \"{}\", it already contains an example. Pass --force to overwrite it.",
+            path.display()
+        );
+        return;
+    }
+
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, input));
+
+    match written {
+        Ok(()) => println!("Wrote example to \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
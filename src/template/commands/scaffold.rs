@@ -1,12 +1,14 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{get_data_path, get_path_for_bin};
+use crate::{Day, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
 #[allow(unused_variables)]
 #[allow(unused_must_use)]
@@ -23,37 +25,50 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const EXAMPLE: &str = "";
+    use advent_of_code::template::examples::{example_answer, read_example};
 
     #[test]
     fn test_part_one() {
-        let result = part_one(EXAMPLE);
-        assert_eq!(result, None);
+        let result = part_one(&read_example(YEAR, DAY, 1));
+        assert_eq!(result.map(|x| x.to_string()), example_answer(YEAR, DAY, 1));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(EXAMPLE);
-        assert_eq!(result, None);
+        let result = part_two(&read_example(YEAR, DAY, 2));
+        assert_eq!(result.map(|x| x.to_string()), example_answer(YEAR, DAY, 2));
     }
 }
 "#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(year: Year, day: Day) {
+    let input_path = get_data_path("inputs", year, &format!("{day}.txt"));
+    let example_path = get_data_path("examples", year, &format!("{day}.txt"));
+    let module_path = get_path_for_bin(year, day);
+
+    for dir in [&input_path, &example_path]
+        .iter()
+        .filter_map(|x| x.parent())
+    {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
-    let mut file = match safe_create_file(&module_path) {
+    let mut file = match safe_create_file(Path::new(&module_path)) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -63,6 +78,7 @@ pub fn handle(day: Day) {
 
     match file.write_all(
         MODULE_TEMPLATE
+            .replace("YEAR_NUMBER", &year.to_string())
             .replace("DAY_NUMBER", &day.into_inner().to_string())
             .as_bytes(),
     ) {
@@ -77,7 +93,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -87,7 +103,7 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -96,5 +112,8 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {} --year {year}` to run your solution.",
+        day.into_inner()
+    );
 }
@@ -1,9 +1,24 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Stdio};
 
-use crate::Day;
+use crate::template::input::InputSource;
+use crate::template::report::Format;
+use crate::template::{cargo_command, get_bin_name};
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    format: Format,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        get_bin_name(year, day),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -20,12 +35,33 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    match input {
+        InputSource::Puzzle => {}
+        InputSource::Example => cmd_args.push("--example".to_string()),
+        InputSource::Path(path) => {
+            cmd_args.push("--input".to_string());
+            cmd_args.push(path.display().to_string());
+        }
+        InputSource::Stdin => {
+            cmd_args.push("--input".to_string());
+            cmd_args.push("-".to_string());
+        }
+    }
+
+    if format != Format::Human {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    let mut cmd = cargo_command()
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,141 @@
+use std::{panic, process};
+
+use crate::template::{
+    answers::{self, Answers},
+    read_file,
+    registry::{self, Solution},
+    report::{Status, PARSE_PART},
+    runner, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, Year};
+
+/// Verifies solutions against their recorded answers via the `solutions` binary.
+pub fn handle(year: Year, day: Option<Day>, is_release: bool) {
+    match registry::build(is_release) {
+        Ok(status) if status.success() => {}
+        _ => {
+            eprintln!("Could not build the solutions binary.");
+            process::exit(1);
+        }
+    }
+
+    let mut args = vec!["verify".to_string(), "--year".into(), year.to_string()];
+
+    if let Some(day) = day {
+        args.push(day.to_string());
+    }
+
+    match registry::run(&args, is_release) {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run solutions: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: u32,
+    failed: u32,
+    missing: u32,
+}
+
+/// Runs every solved day of `year` (or only `day`) in-process and compares the results against the recorded answers.
+/// Exits with a non-zero status if any answer does not match. This is the entry point of `solutions verify`.
+pub fn run(solutions: &[Solution], year: Year, day: Option<Day>) {
+    runner::set_quiet(true);
+
+    let solutions: Vec<&Solution> = solutions
+        .iter()
+        .filter(|x| x.year == year && day.is_none_or(|day| x.day == day))
+        .collect();
+
+    if let (Some(day), true) = (day, solutions.is_empty()) {
+        eprintln!("Day {day} of {year} has not been scaffolded.");
+        process::exit(1);
+    }
+
+    let mut summary = Summary::default();
+
+    for (i, solution) in solutions.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", solution.day);
+        println!("------");
+
+        let answers = answers::load(solution.year, solution.day).unwrap_or_else(|e| {
+            eprintln!("Failed to read recorded answers: {e}");
+            process::exit(1);
+        });
+
+        verify_day(solution, &answers, &mut summary);
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Passed:{ANSI_RESET} {}, {ANSI_BOLD}failed:{ANSI_RESET} {}, {ANSI_BOLD}missing:{ANSI_RESET} {}",
+        summary.passed, summary.failed, summary.missing
+    );
+
+    if summary.failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn verify_day(solution: &Solution, answers: &Answers, summary: &mut Summary) {
+    let input = match read_file("inputs", solution.year, solution.day) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("Skipped: {e}");
+            summary.missing += 2;
+            return;
+        }
+    };
+
+    let Ok(reports) = panic::catch_unwind(|| (solution.run)(&input)) else {
+        for part in 1..=2 {
+            if answers.get(part).is_some() {
+                println!("Part {part}: ✖ could not run solution");
+                summary.failed += 1;
+            } else {
+                println!("Part {part}: ? could not run solution, no recorded answer");
+                summary.missing += 1;
+            }
+        }
+        return;
+    };
+
+    for report in reports.iter().filter(|x| x.part != PARSE_PART) {
+        let part = report.part;
+        let actual = report.answer.as_deref();
+        let unsolved = match (report.status, &report.error) {
+            (Status::Panicked, Some(error)) => format!("panicked at {error}"),
+            (Status::Skipped, Some(reason)) => format!("skipped, {reason}"),
+            (_, Some(error)) => format!("error: {error}"),
+            (_, None) => "not solved".into(),
+        };
+
+        match (answers.get(part), actual) {
+            (None, actual) => {
+                println!(
+                    "Part {part}: ? {} (no recorded answer)",
+                    actual.unwrap_or(&unsolved)
+                );
+                summary.missing += 1;
+            }
+            (Some(expected), Some(actual)) if expected == actual => {
+                println!("Part {part}: ✔ {actual}");
+                summary.passed += 1;
+            }
+            (Some(expected), actual) => {
+                println!(
+                    "Part {part}: ✖ {} (expected {expected})",
+                    actual.unwrap_or(&unsolved)
+                );
+                summary.failed += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+/// Module that extracts example inputs and their expected answers from puzzle descriptions.
+///
+/// Examples live in `data/examples/YYYY/DD.txt`, with `DD-2.txt` holding the example of part two if it differs.
+/// Expected answers are kept next to them in `DD.toml`, using the format of [`answers`].
+use std::{fs, path::PathBuf, sync::OnceLock};
+
+use regex::Regex;
+
+use crate::template::answers::{self, Answers};
+use crate::template::get_data_path;
+use crate::template::input::normalize;
+use crate::{Day, Year};
+
+/// The example of a single part, as found in the puzzle description.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// The examples of both parts of a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub part_1: Example,
+    pub part_2: Example,
+}
+
+/// Extracts the examples from the Markdown description of a puzzle.
+///
+/// The example input is the first code block following a mention of "example", or the first code block of a part.
+/// The expected answer is the last emphasized inline code of a part, which is where puzzles state the example result.
+#[must_use]
+pub fn extract(markdown: &str) -> Examples {
+    let (part_1, part_2) = split_parts(markdown);
+
+    Examples {
+        part_1: extract_part(part_1),
+        part_2: part_2.map(extract_part).unwrap_or_default(),
+    }
+}
+
+fn split_parts(markdown: &str) -> (&str, Option<&str>) {
+    let mut offset = 0;
+
+    for line in markdown.split_inclusive('\n') {
+        if line.starts_with('#') && line.contains("Part Two") {
+            return (&markdown[..offset], Some(&markdown[offset..]));
+        }
+        offset += line.len();
+    }
+
+    (markdown, None)
+}
+
+fn extract_part(section: &str) -> Example {
+    Example {
+        input: find_example_input(section),
+        answer: find_emphasized_code(section).last().cloned(),
+    }
+}
+
+fn find_example_input(section: &str) -> Option<String> {
+    let mut blocks = vec![];
+    let mut mentions_example = false;
+    let mut block: Option<(bool, Vec<&str>)> = None;
+
+    for line in section.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match block.as_mut() {
+            Some((after_example, lines)) if is_fence => {
+                blocks.push((*after_example, lines.join("\n") + "\n"));
+                block = None;
+            }
+            Some((_, lines)) => lines.push(line),
+            None if is_fence => {
+                block = Some((mentions_example, vec![]));
+                mentions_example = false;
+            }
+            None => mentions_example |= line.to_lowercase().contains("example"),
+        }
+    }
+
+    blocks
+        .iter()
+        .find(|(after_example, _)| *after_example)
+        .or(blocks.first())
+        .map(|(_, block)| block.clone())
+}
+
+fn find_emphasized_code(section: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // matches *`x`*, **`x`**, `*x*` and `**x**`.
+    let re =
+        RE.get_or_init(|| Regex::new(r"\*{1,2}`([^`]+)`\*{1,2}|`\*{1,2}([^`*]+)\*{1,2}`").unwrap());
+
+    re.captures_iter(section)
+        .filter_map(|c| c.get(1).or(c.get(2)))
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+#[must_use]
+pub fn get_path_for_example(year: Year, day: Day, part: u8) -> PathBuf {
+    let file_name = match part {
+        1 => format!("{day}.txt"),
+        _ => format!("{day}-{part}.txt"),
+    };
+
+    get_data_path("examples", year, &file_name)
+}
+
+#[must_use]
+pub fn get_path_for_example_answers(year: Year, day: Day) -> PathBuf {
+    get_data_path("examples", year, &format!("{day}.toml"))
+}
+
+/// Reads the example of a part. Part two falls back to the example of part one if it has none of its own.
+///
+/// # Panics
+/// Panics if the example file does not exist.
+#[must_use]
+pub fn read_example(year: Year, day: Day, part: u8) -> String {
+    let path = get_path_for_example(year, day, part);
+    let path = if path.exists() {
+        path
+    } else {
+        get_path_for_example(year, day, 1)
+    };
+
+    normalize(&fs::read_to_string(path).expect("could not open example file"))
+}
+
+/// Returns the expected answer of a part's example, if one is recorded.
+#[must_use]
+pub fn example_answer(year: Year, day: Day, part: u8) -> Option<String> {
+    load_answers(year, day).ok()?.get(part).map(String::from)
+}
+
+/// Loads the expected answers recorded for the examples of a day.
+pub fn load_answers(year: Year, day: Day) -> Result<Answers, std::io::Error> {
+    answers::read(&get_path_for_example_answers(year, day))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, Example};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+Something is *wrong*.
+
+```
+not an example
+```
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the values are `12` and *`38`*. Adding these together produces *`142`*.
+
+## --- Part Two ---
+
+Your calculation isn't quite right.
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces **`281`**.
+";
+
+    #[test]
+    fn extract_both_parts() {
+        let examples = extract(PUZZLE);
+
+        assert_eq!(
+            examples.part_1,
+            Example {
+                input: Some("1abc2\npqr3stu8vwx\n".into()),
+                answer: Some("142".into()),
+            }
+        );
+        assert_eq!(
+            examples.part_2,
+            Example {
+                input: Some("two1nine\neightwothree\n".into()),
+                answer: Some("281".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn extract_first_part_only() {
+        let part_1 = PUZZLE.split("## --- Part Two").next().unwrap();
+        let examples = extract(part_1);
+
+        assert_eq!(examples.part_1.answer, Some("142".into()));
+        assert_eq!(examples.part_2, Example::default());
+    }
+
+    #[test]
+    fn extract_shared_example() {
+        let examples = extract(
+            "Example:\n\n```\n1 2\n```\n\nResult is `*3*`.\n\n## --- Part Two ---\n\nNow it is *`4`*.\n",
+        );
+
+        assert_eq!(examples.part_1.input, Some("1 2\n".into()));
+        assert_eq!(examples.part_1.answer, Some("3".into()));
+        assert_eq!(examples.part_2.input, None);
+        assert_eq!(examples.part_2.answer, Some("4".into()));
+    }
+}
@@ -0,0 +1,299 @@
+/// Selects the input a solution is run against.
+///
+/// By default, solutions read their puzzle input from `data/inputs`. Passing `--example` runs them against the
+/// example in `data/examples` instead, `--input <path>` against any file and `--input -` against stdin.
+///
+/// Every input is checked for being empty or a placeholder, and normalized to `\n` line endings with a single
+/// trailing newline, so parsers behave the same regardless of how the file was created.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::commands::download;
+use crate::template::examples::get_path_for_example;
+use crate::template::get_data_path;
+use crate::template::report::{self, Format};
+use crate::{Day, Year};
+
+/// Messages the Advent of Code website serves instead of an input, e.g. when not logged in.
+const PLACEHOLDERS: &[&str] = &[
+    "Puzzle inputs differ by user",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+    "<!DOCTYPE html>",
+];
+
+/// An error which can be returned when reading an input. Every variant holds a description of the input's source.
+#[derive(Debug)]
+pub enum ReadFileError {
+    NotFound(String),
+    Empty(String),
+    Placeholder(String),
+    IO(String, io::Error),
+}
+
+impl Error for ReadFileError {}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadFileError::NotFound(source) => write!(f, "\"{source}\" does not exist."),
+            ReadFileError::Empty(source) => write!(f, "\"{source}\" is empty."),
+            ReadFileError::Placeholder(source) => write!(
+                f,
+                "\"{source}\" contains a message of the Advent of Code website instead of a puzzle input."
+            ),
+            ReadFileError::IO(source, e) => write!(f, "could not read \"{source}\": {e}"),
+        }
+    }
+}
+
+/// Normalizes line endings to `\n` and ends the input with exactly one newline.
+#[must_use]
+pub fn normalize(input: &str) -> String {
+    let mut normalized = input
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string();
+    normalized.push('\n');
+    normalized
+}
+
+/// Checks that an input is neither empty nor a placeholder, and normalizes it.
+fn check(input: &str, source: &str) -> Result<String, ReadFileError> {
+    if input.trim().is_empty() {
+        return Err(ReadFileError::Empty(source.into()));
+    }
+
+    if PLACEHOLDERS
+        .iter()
+        .any(|x| input.trim_start().starts_with(x))
+    {
+        return Err(ReadFileError::Placeholder(source.into()));
+    }
+
+    Ok(normalize(input))
+}
+
+/// Reads, checks and normalizes an input file.
+pub fn read_path(path: &Path) -> Result<String, ReadFileError> {
+    let source = path.display().to_string();
+
+    match fs::read_to_string(path) {
+        Ok(input) => check(&input, &source),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(ReadFileError::NotFound(source)),
+        Err(e) => Err(ReadFileError::IO(source, e)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    #[default]
+    Puzzle,
+    Example,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the input source from the arguments passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::parse(&args)
+    }
+
+    fn parse(args: &[String]) -> Self {
+        if let Some(value) = args
+            .iter()
+            .position(|x| x == "--input")
+            .and_then(|i| args.get(i + 1))
+        {
+            return match value.as_str() {
+                "-" => InputSource::Stdin,
+                path => InputSource::Path(path.into()),
+            };
+        }
+
+        if args.iter().any(|x| x == "--example") {
+            return InputSource::Example;
+        }
+
+        InputSource::Puzzle
+    }
+
+    /// Whether this is the puzzle input, i.e. whether answers computed from it can be submitted.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Describes where the input is read from.
+    #[must_use]
+    pub fn label(&self, year: Year, day: Day) -> String {
+        match self {
+            InputSource::Puzzle => get_data_path("inputs", year, &format!("{day}.txt"))
+                .display()
+                .to_string(),
+            InputSource::Example => get_path_for_example(year, day, 1).display().to_string(),
+            InputSource::Path(path) => path.display().to_string(),
+            InputSource::Stdin => "stdin".into(),
+        }
+    }
+
+    /// Reads the input of a day from this source.
+    pub fn read(&self, year: Year, day: Day) -> Result<String, ReadFileError> {
+        match self {
+            InputSource::Puzzle => read_path(&get_data_path("inputs", year, &format!("{day}.txt"))),
+            InputSource::Example => read_path(&get_path_for_example(year, day, 1)),
+            InputSource::Path(path) => read_path(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| ReadFileError::IO("stdin".into(), e))?;
+                check(&input, "stdin")
+            }
+        }
+    }
+
+    /// Suggests how to fix a missing or invalid input, if there is a command for it.
+    fn hint(&self, year: Year, day: Day) -> Option<String> {
+        let day = day.into_inner();
+        match self {
+            InputSource::Puzzle => Some(format!(
+                "Run `cargo download {day} --year {year}` to download your puzzle input."
+            )),
+            InputSource::Example => Some(format!(
+                "Run `cargo examples {day} --year {year}` to extract the example from the puzzle description."
+            )),
+            InputSource::Path(_) | InputSource::Stdin => None,
+        }
+    }
+}
+
+/// Asks whether to download a missing puzzle input, if the user is there to answer.
+fn offer_download(year: Year, day: Day) -> bool {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return false;
+    }
+
+    print!("Download it now? [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+        return false;
+    }
+
+    download::handle(year, day);
+    true
+}
+
+/// Reads the input selected by the arguments of the current process.
+/// Inputs other than the puzzle input are announced, so their results are not mistaken for real answers.
+///
+/// Exits the process with a diagnostic if the input can not be read. A missing puzzle input can be downloaded
+/// interactively.
+#[must_use]
+pub fn read_input(year: Year, day: Day) -> String {
+    let source = InputSource::from_args();
+    let label = source.label(year, day);
+
+    if !source.is_puzzle() {
+        report::info(Format::from_args(), &format!("Input: {label}"));
+    }
+
+    let error = match source.read(year, day) {
+        Ok(input) => return input,
+        Err(e) => e,
+    };
+
+    eprintln!("Error: {error}");
+
+    if let Some(hint) = source.hint(year, day) {
+        eprintln!("{hint}");
+    }
+
+    let is_recoverable = !matches!(error, ReadFileError::IO(..));
+    if source.is_puzzle() && is_recoverable && offer_download(year, day) {
+        match source.read(year, day) {
+            Ok(input) => return input,
+            Err(e) => eprintln!("Error: {e}"),
+        }
+    }
+
+    process::exit(1);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, normalize, InputSource, ReadFileError};
+    use crate::{day, year};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_input_source() {
+        assert_eq!(InputSource::parse(&args(&["05"])), InputSource::Puzzle);
+        assert_eq!(
+            InputSource::parse(&args(&["05", "--time", "--example"])),
+            InputSource::Example
+        );
+        assert_eq!(
+            InputSource::parse(&args(&["05", "--input", "other.txt"])),
+            InputSource::Path("other.txt".into())
+        );
+        assert_eq!(
+            InputSource::parse(&args(&["05", "--input", "-"])),
+            InputSource::Stdin
+        );
+    }
+
+    #[test]
+    fn normalize_input() {
+        assert_eq!(normalize("1\r\n2\r\n"), "1\n2\n");
+        assert_eq!(normalize("1\n2"), "1\n2\n");
+        assert_eq!(normalize("1\n2\n\n\n"), "1\n2\n");
+    }
+
+    #[test]
+    fn check_input() {
+        assert_eq!(check("1 2\r\n", "a").unwrap(), "1 2\n");
+        assert!(matches!(check("", "a"), Err(ReadFileError::Empty(_))));
+        assert!(matches!(check(" \n\n", "a"), Err(ReadFileError::Empty(_))));
+        assert!(matches!(
+            check(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+                "a"
+            ),
+            Err(ReadFileError::Placeholder(_))
+        ));
+    }
+
+    #[test]
+    fn label_input_source() {
+        assert_eq!(
+            InputSource::Example.label(year!(2023), day!(2023, 5)),
+            "data/examples/2023/05.txt"
+        );
+        assert_eq!(
+            InputSource::Stdin.label(year!(2023), day!(2023, 5)),
+            "stdin"
+        );
+        assert_eq!(InputSource::Puzzle.is_puzzle(), true);
+        assert_eq!(InputSource::Example.is_puzzle(), false);
+    }
+}
@@ -0,0 +1,422 @@
+/// Module that keeps a ledger of every submitted answer in `data/submissions/YYYY/DD.jsonl`.
+/// The ledger is used to refuse submissions that are already known to be wrong, which avoids the
+/// escalating lockouts that Advent of Code imposes after wrong answers.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use tinyjson::JsonValue;
+
+use crate::template::get_data_path;
+use crate::template::report::{ordered_object, stringify};
+use crate::{Day, Year};
+
+/// The verdict on a submitted answer, as parsed from the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was not checked because the previous submission was too recent.
+    TooRecent,
+    /// The answer was not checked because the part has already been completed.
+    AlreadyCompleted,
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the verdict from the response text (or html) of a submission.
+    #[must_use]
+    pub fn parse(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if response.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadyCompleted
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::TooRecent => "too recent",
+            Verdict::AlreadyCompleted => "already completed",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+            Verdict::TooRecent,
+            Verdict::AlreadyCompleted,
+            Verdict::Unknown,
+        ]
+        .into_iter()
+        .find(|x| x.as_str() == s)
+    }
+
+    /// Whether the verdict tells that the answer is not correct.
+    #[must_use]
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses the time to wait before the next submission from the response of a submission.
+#[must_use]
+pub fn parse_wait_seconds(response: &str) -> Option<u64> {
+    static LEFT_TO_WAIT: OnceLock<Regex> = OnceLock::new();
+    static WAIT_BEFORE: OnceLock<Regex> = OnceLock::new();
+
+    let left_to_wait = LEFT_TO_WAIT
+        .get_or_init(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+    if let Some(captures) = left_to_wait.captures(response) {
+        let minutes: u64 = captures
+            .get(1)
+            .map_or(Some(0), |x| x.as_str().parse().ok())?;
+        let seconds: u64 = captures[2].parse().ok()?;
+        return Some(minutes * 60 + seconds);
+    }
+
+    let wait_before = WAIT_BEFORE.get_or_init(|| {
+        Regex::new(r"(?i)please wait (one|\d+) minutes? before trying again").unwrap()
+    });
+    let captures = wait_before.captures(response)?;
+    let minutes: u64 = match &captures[1] {
+        "one" => 1,
+        x => x.parse().ok()?,
+    };
+    Some(minutes * 60)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A submitted answer and its verdict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds to wait before the next submission is accepted, if the response asked for it.
+    pub wait_seconds: Option<u64>,
+    /// Unix timestamp of the submission.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    /// Creates a submission from the response to submitting `answer`.
+    #[must_use]
+    pub fn from_response(part: u8, answer: &str, response: &str) -> Self {
+        Self {
+            part,
+            answer: answer.into(),
+            verdict: Verdict::parse(response),
+            wait_seconds: parse_wait_seconds(response),
+            timestamp: now(),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn to_json(&self) -> String {
+        ordered_object(&[
+            ("part", stringify(f64::from(self.part).into())),
+            ("answer", stringify(self.answer.clone().into())),
+            (
+                "verdict",
+                stringify(self.verdict.as_str().to_string().into()),
+            ),
+            (
+                "wait_seconds",
+                stringify(
+                    self.wait_seconds
+                        .map_or(JsonValue::Null, |x| (x as f64).into()),
+                ),
+            ),
+            ("timestamp", stringify((self.timestamp as f64).into())),
+        ])
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_json(s: &str) -> Option<Self> {
+        let value: JsonValue = s.trim().parse().ok()?;
+        let map: &HashMap<String, JsonValue> = value.get()?;
+        let number = |key: &str| map.get(key).and_then(|x| x.get::<f64>()).copied();
+
+        Some(Self {
+            part: number("part")? as u8,
+            answer: map.get("answer")?.get::<String>()?.clone(),
+            verdict: Verdict::from_str(map.get("verdict")?.get::<String>()?)?,
+            wait_seconds: number("wait_seconds").map(|x| x as u64),
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect(String),
+    KnownWrong(Verdict),
+    AboveTooHigh(String),
+    BelowTooLow(String),
+    Cooldown(u64),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and is {verdict}.")
+            }
+            Refusal::AboveTooHigh(bound) => {
+                write!(
+                    f,
+                    "this answer is not below {bound}, which is known to be too high."
+                )
+            }
+            Refusal::BelowTooLow(bound) => {
+                write!(
+                    f,
+                    "this answer is not above {bound}, which is known to be too low."
+                )
+            }
+            Refusal::Cooldown(seconds) => {
+                write!(f, "wait another {seconds}s before submitting again.")
+            }
+        }
+    }
+}
+
+/// Every recorded submission for a day.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Checks whether `answer` may be submitted for `part` at unix time `now`.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(wait) = self
+            .submissions
+            .iter()
+            .filter_map(|x| Some(x.timestamp + x.wait_seconds?))
+            .max()
+            .filter(|until| *until > now)
+        {
+            return Err(Refusal::Cooldown(wait - now));
+        }
+
+        let submissions: Vec<&Submission> =
+            self.submissions.iter().filter(|x| x.part == part).collect();
+
+        if let Some(correct) = submissions.iter().find(|x| x.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadyCorrect(correct.answer.clone()));
+        }
+
+        if let Some(known) = submissions
+            .iter()
+            .find(|x| x.answer == answer && x.verdict.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(known.verdict));
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            submissions
+                .iter()
+                .filter(move |x| x.verdict == verdict)
+                .filter_map(|x| x.answer.trim().parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|x| value >= *x) {
+            return Err(Refusal::AboveTooHigh(too_high.to_string()));
+        }
+
+        if let Some(too_low) = bound(Verdict::TooLow).max().filter(|x| value <= *x) {
+            return Err(Refusal::BelowTooLow(too_low.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn get_path_for_ledger(year: Year, day: Day) -> PathBuf {
+    get_data_path("submissions", year, &format!("{day}.jsonl"))
+}
+
+/// Loads the ledger for a day. Days without a file have no submissions.
+pub fn load(year: Year, day: Day) -> Result<Ledger, io::Error> {
+    match fs::read_to_string(get_path_for_ledger(year, day)) {
+        Ok(s) => Ok(Ledger {
+            submissions: s.lines().filter_map(Submission::from_json).collect(),
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends a submission to the ledger of a day.
+pub fn record(year: Year, day: Day, submission: &Submission) -> Result<(), io::Error> {
+    let path = get_path_for_ledger(year, day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", submission.to_json())
+}
+
+/// The current unix timestamp in seconds.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait_seconds, Ledger, Refusal, Submission, Verdict};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            part,
+            answer: answer.into(),
+            verdict,
+            wait_seconds: None,
+            timestamp: 1000,
+        }
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        let too_high = "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data; there are also some general tips on the about page, or you can ask for hints on the subreddit. Please wait one minute before trying again. [Return to Day 1]";
+        assert_eq!(Verdict::parse(too_high), Verdict::TooHigh);
+        assert_eq!(parse_wait_seconds(too_high), Some(60));
+
+        let too_low = "That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.";
+        assert_eq!(Verdict::parse(too_low), Verdict::TooLow);
+        assert_eq!(parse_wait_seconds(too_low), Some(300));
+
+        let wrong = "That's not the right answer. If you're stuck, make sure you're using the full input data.";
+        assert_eq!(Verdict::parse(wrong), Verdict::Wrong);
+        assert_eq!(parse_wait_seconds(wrong), None);
+
+        let recent = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.";
+        assert_eq!(Verdict::parse(recent), Verdict::TooRecent);
+        assert_eq!(parse_wait_seconds(recent), Some(65));
+
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadyCompleted
+        );
+    }
+
+    #[test]
+    fn roundtrip_submission() {
+        let mut s = submission(2, "a \"quoted\" answer", Verdict::TooLow);
+        s.wait_seconds = Some(60);
+        assert_eq!(Submission::from_json(&s.to_json()), Some(s));
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let ledger = Ledger {
+            submissions: vec![
+                submission(1, "abc", Verdict::Wrong),
+                submission(2, "42", Verdict::Correct),
+            ],
+        };
+
+        assert_eq!(
+            ledger.check(1, "abc", 2000),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(ledger.check(1, "abd", 2000), Ok(()));
+        assert_eq!(
+            ledger.check(2, "43", 2000),
+            Err(Refusal::AlreadyCorrect("42".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = Ledger {
+            submissions: vec![
+                submission(1, "100", Verdict::TooHigh),
+                submission(1, "150", Verdict::TooHigh),
+                submission(1, "20", Verdict::TooLow),
+                submission(2, "10", Verdict::TooHigh),
+            ],
+        };
+
+        assert_eq!(
+            ledger.check(1, "100", 2000),
+            Err(Refusal::KnownWrong(Verdict::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(1, "120", 2000),
+            Err(Refusal::AboveTooHigh("100".into()))
+        );
+        assert_eq!(
+            ledger.check(1, "-5", 2000),
+            Err(Refusal::BelowTooLow("20".into()))
+        );
+        assert_eq!(ledger.check(1, "21", 2000), Ok(()));
+        assert_eq!(ledger.check(1, "99", 2000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let mut wrong = submission(1, "5", Verdict::Wrong);
+        wrong.wait_seconds = Some(60);
+        let ledger = Ledger {
+            submissions: vec![wrong],
+        };
+
+        assert_eq!(ledger.check(2, "7", 1030), Err(Refusal::Cooldown(30)));
+        assert_eq!(ledger.check(2, "7", 1060), Ok(()));
+    }
+}
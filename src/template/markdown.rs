@@ -0,0 +1,245 @@
+//! Converts the html of Advent of Code puzzle pages to Markdown.
+//! Only the handful of tags used in puzzle descriptions are supported, everything else is reduced to its text.
+
+/// Converts every `<article>` of a puzzle page to Markdown.
+/// Relative links are resolved against `base_url`.
+#[must_use]
+pub fn articles_to_markdown(html: &str, base_url: &str) -> String {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+
+        articles.push(to_markdown(&rest[start..start + end], base_url));
+        rest = &rest[start + end + "</article>".len()..];
+    }
+
+    articles.join("\n\n")
+}
+
+enum Token<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let Some(end) = tag.find('>') else {
+                tokens.push(Token::Text(rest));
+                break;
+            };
+
+            let inner = tag[..end].trim_end_matches('/').trim();
+            if let Some(name) = inner.strip_prefix('/') {
+                tokens.push(Token::Close(name.trim()));
+            } else {
+                let (name, attrs) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+                tokens.push(Token::Open(name, attrs));
+            }
+
+            rest = &tag[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+fn get_attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = attrs[start..].find('"')?;
+    Some(&attrs[start..start + end])
+}
+
+/// Decodes the html entities that appear in puzzle descriptions.
+#[must_use]
+pub fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => {
+                    let code = match entity.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn to_markdown(html: &str, base_url: &str) -> String {
+    let mut out = String::new();
+
+    let mut in_pre = false;
+    // start position and emphasis of the inline code span that is currently open.
+    let mut code: Option<(usize, bool)> = None;
+    let mut links: Vec<String> = vec![];
+
+    for token in tokenize(html) {
+        match token {
+            Token::Open("h2", _) => out.push_str("## "),
+            Token::Close("h2" | "p") => out.push_str("\n\n"),
+            Token::Open("pre", _) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            Token::Close("pre") => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            Token::Open("code", _) if !in_pre => {
+                code = Some((out.len(), false));
+                out.push('`');
+            }
+            Token::Close("code") if !in_pre => {
+                out.push('`');
+                // markdown does not render emphasis within code, emphasize the whole span instead.
+                if let Some((start, true)) = code {
+                    out.insert(start, '*');
+                    out.push('*');
+                }
+                code = None;
+            }
+            Token::Open("em", _) | Token::Close("em") if !in_pre => match code.as_mut() {
+                Some((_, emphasized)) => *emphasized = true,
+                None => out.push('*'),
+            },
+            Token::Open("li", _) => out.push_str("- "),
+            Token::Close("li") => out.push('\n'),
+            Token::Close("ul") => out.push('\n'),
+            Token::Open("br", _) => out.push('\n'),
+            Token::Open("a", attrs) => {
+                let href = get_attr(attrs, "href").unwrap_or_default();
+                let href = if href.starts_with('/') {
+                    format!("{}{href}", base_url.trim_end_matches('/'))
+                } else {
+                    href.to_string()
+                };
+                links.push(decode_entities(&href));
+                out.push('[');
+            }
+            Token::Close("a") => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                // skip whitespace between block elements.
+                if in_pre || !(text.trim().is_empty() && (out.is_empty() || out.ends_with('\n'))) {
+                    out.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut markdown = out.trim().to_string();
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+    markdown
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles_to_markdown, decode_entities};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &quot;c&quot; &#39;d&#x27; &foo"),
+            "a <b> & \"c\" 'd' &foo"
+        );
+    }
+
+    #[test]
+    fn converts_puzzle() {
+        let html = r#"<main><script>ignored</script>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href="/2023/support" target="_blank">AoC++</a>.</p>
+<p>For example:</p>
+<pre><code>1abc2
+a&lt;b
+</code></pre>
+<ul>
+<li>first <code>12</code></li>
+<li>second</li>
+</ul>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54927</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now <em><code>281</code></em>.</p></article>
+</main>"#;
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "Something is *wrong* with [AoC++](https://adventofcode.com/2023/support).",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "a<b",
+            "```",
+            "",
+            "- first `12`",
+            "- second",
+            "",
+            "Adding these together produces *`142`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Now *`281`*.",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            articles_to_markdown(html, "https://adventofcode.com/"),
+            expected
+        );
+    }
+}
@@ -1,36 +1,161 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Year};
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bench_export;
+pub mod bench_history;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod ledger;
+pub mod markdown;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Returns the name of the binary that holds the solution of a day, e.g. `2023-05`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn get_bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+#[must_use]
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(year, day))
+}
+
+/// Returns the path of a file in the data directory of a year, e.g. `data/inputs/2023/05.txt`.
+#[must_use]
+pub fn get_data_path(folder: &str, year: Year, file_name: &str) -> PathBuf {
+    PathBuf::from("data")
+        .join(folder)
+        .join(year.to_string())
+        .join(file_name)
+}
+
+/// Variables that cargo sets for the binary it runs, besides the `CARGO_PKG_*` ones.
+const CARGO_RUN_VARS: [&str; 6] = [
+    "OUT_DIR",
+    "CARGO_MANIFEST_DIR",
+    "CARGO_MANIFEST_PATH",
+    "CARGO_CRATE_NAME",
+    "CARGO_BIN_NAME",
+    "CARGO_PRIMARY_PACKAGE",
+];
+
+/// Creates a `cargo` command that does not inherit the variables cargo set for the running binary.
+/// Build scripts that track them, such as the one of `ring`, would otherwise rerun in every nested build.
+#[must_use]
+pub fn cargo_command() -> Command {
+    let mut cmd = Command::new("cargo");
+
+    for (key, _) in env::vars_os() {
+        let Some(key) = key.to_str() else {
+            continue;
+        };
+
+        if key.starts_with("CARGO_PKG_") || CARGO_RUN_VARS.contains(&key) {
+            cmd.env_remove(key);
+        }
+    }
+
+    cmd
+}
+
+/// Helper function that reads a data file of a day, see [`input::read_path`] for the checks applied to it.
+pub fn read_file(folder: &str, year: Year, day: Day) -> Result<String, input::ReadFileError> {
+    input::read_path(&get_data_path(folder, year, &format!("{day}.txt")))
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+///
+/// By default, `part_one` and `part_two` receive the raw input. If a `parse` function is passed as a third argument,
+/// the input is parsed once, the parts receive a reference to its output and parsing is timed separately:
+///
+/// ```ignore
+/// advent_of_code::solution!(2023, 8, parse);
+///
+/// fn parse(input: &str) -> Vec<u32> { .. }
+/// fn part_one(input: &Vec<u32>) -> Option<u32> { .. }
+/// ```
+///
+/// Parts return either `Option<T>` or `Result<T, E>`, see [`runner::PartResult`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($year:expr, $day:expr) => {
+        /// The year of the event.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($year, $day);
+
+        /// Registers this solution with the `solutions` binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution = {
+            fn run(input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+                use advent_of_code::template::runner::*;
+                vec![
+                    run_part(part_one, input, YEAR, DAY, 1),
+                    run_part(part_two, input, YEAR, DAY, 2),
+                ]
+            }
+
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                run,
+            }
+        };
+
+        fn main() {
+            let input = advent_of_code::template::input::read_input(YEAR, DAY);
+            (SOLUTION.run)(&input);
+        }
+    };
+    ($year:expr, $day:expr, $parse:path) => {
+        /// The year of the event.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
         /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        const DAY: advent_of_code::Day = advent_of_code::day!($year, $day);
+
+        /// Registers this solution with the `solutions` binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution = {
+            fn run(input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+                use advent_of_code::template::runner::*;
+                let (parsed, parse_report) = run_parse($parse, input, YEAR, DAY);
+                match parsed {
+                    Some(parsed) => vec![
+                        parse_report,
+                        run_part(part_one, &parsed, YEAR, DAY, 1),
+                        run_part(part_two, &parsed, YEAR, DAY, 2),
+                    ],
+                    None => vec![
+                        parse_report,
+                        skip_part(YEAR, DAY, 1),
+                        skip_part(YEAR, DAY, 2),
+                    ],
+                }
+            }
+
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                run,
+            }
+        };
 
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = advent_of_code::template::input::read_input(YEAR, DAY);
+            (SOLUTION.run)(&input);
         }
     };
 }
@@ -2,7 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::get_path_for_bin;
+use crate::template::report::PARSE_PART;
+use crate::template::runner::Stats;
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -18,24 +21,55 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Timing of the parse phase, for solutions that parse their input once.
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// Creates the timings of a day, its total is the sum of the medians of its parts.
+    #[must_use]
+    pub fn new(
+        day: Day,
+        parse: Option<Stats>,
+        part_1: Option<Stats>,
+        part_2: Option<Stats>,
+    ) -> Self {
+        Self {
+            day,
+            parse,
+            part_1,
+            part_2,
+            total_nanos: [parse, part_1, part_2]
+                .iter()
+                .flatten()
+                .map(|x| x.median.as_secs_f64() * 1_000_000_000_f64)
+                .sum(),
+        }
+    }
+
+    /// The timing of a part, see [`PARSE_PART`].
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&Stats> {
+        match part {
+            PARSE_PART => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -58,73 +92,155 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_stats(stats: Option<Stats>) -> String {
+    stats.map_or_else(|| "-".into(), |x| format!("{:.1?}", x.median))
+}
+
+/// Splits the existing table into the sections of each year, keyed by their `### YYYY` heading.
+/// Content outside of a year section (e.g. a table written before years were tracked) is dropped.
+fn parse_year_sections(table: &str) -> Vec<(Year, String)> {
+    let mut sections: Vec<(Year, Vec<&str>)> = vec![];
+
+    for line in table.lines() {
+        if line == MARKER {
+            continue;
+        }
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+        match line
+            .strip_prefix("### ")
+            .and_then(|x| x.trim().parse().ok())
+        {
+            Some(year) => sections.push((year, vec![])),
+            None => {
+                if let Some((_, lines)) = sections.last_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(year, lines)| (year, lines.join("\n").trim().to_string()))
+        .collect()
+}
+
+fn construct_year_table(year: Year, timings: Vec<Timings>, total_millis: f64) -> String {
+    // only show the parse column if a solution of the year parses its input separately.
+    let has_parse = timings.iter().any(|x| x.parse.is_some());
+
+    let mut lines: Vec<String> = if has_parse {
+        vec![
+            "| Day | Parse | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---: | :---:  |".into(),
+        ]
+    } else {
+        vec![
+            "| Day | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---:  |".into(),
+        ]
+    };
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(year, timing.day);
+        let parse = if has_parse {
+            format!(" `{}` |", format_stats(timing.parse))
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{parse} `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_stats(timing.part_1),
+            format_stats(timing.part_2)
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    lines.join("\n")
+}
+
+fn construct_table(prefix: &str, sections: Vec<(Year, String)>) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+
+    for (year, section) in sections {
+        lines.push(String::new());
+        lines.push(format!("{prefix}# {year}"));
+        lines.push(String::new());
+        lines.push(section);
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+
+    let mut sections = parse_year_sections(&s[positions.pos_start..positions.pos_end]);
+    sections.retain(|(x, _)| *x != year);
+    sections.push((year, construct_year_table(year, timings, total_millis)));
+    // newest year first.
+    sections.sort_by_key(|(year, _)| std::cmp::Reverse(*year));
+
+    let table = construct_table("##", sections);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Updates the benchmarks of `year` in the README, keeping the benchmarks of other years.
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
-mod tests {
+pub(crate) mod tests {
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::template::runner::Stats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn ms(millis: u64) -> Option<Stats> {
+        Some(Stats::single(Duration::from_millis(millis)))
+    }
 
-    fn get_mock_timings() -> Vec<Timings> {
+    pub(crate) fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                day: day!(2023, 1),
+                parse: None,
+                part_1: ms(10),
+                part_2: ms(20),
                 total_nanos: 3e+10,
             },
             Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                day: day!(2023, 2),
+                parse: None,
+                part_1: ms(30),
+                part_2: ms(40),
                 total_nanos: 7e+10,
             },
             Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                day: day!(2023, 4),
+                parse: None,
+                part_1: ms(40),
+                part_2: ms(50),
                 total_nanos: 9e+10,
             },
         ]
@@ -134,28 +250,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,18 +279,20 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "### 2023",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +301,41 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn keeps_benchmarks_of_other_years() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2022), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings()[..1].to_vec(), 30.0).unwrap();
+        update_content(&mut s, year!(2022), get_mock_timings()[..2].to_vec(), 100.0).unwrap();
+
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+        assert_eq!(s.matches("### 2023").count(), 1);
+        assert_eq!(s.matches("### 2022").count(), 1);
+        assert_eq!(s.find("### 2023") < s.find("### 2022"), true);
+        assert_eq!(s.contains("**Total: 30.00ms**"), true);
+        assert_eq!(s.contains("**Total: 100.00ms**"), true);
+        assert_eq!(s.contains("**Total: 190.00ms**"), false);
+        assert_eq!(s.contains("./src/bin/2022-02.rs"), true);
+        assert_eq!(s.contains("./src/bin/2022-04.rs"), false);
+    }
+
+    #[test]
+    fn format_parse_timings() {
+        let mut timings = get_mock_timings();
+        timings[1].parse = ms(5);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 195.0).unwrap();
+
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2023-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"),
+            true
+        );
+    }
 }
@@ -0,0 +1,55 @@
+/// Registry of solutions, used to run every solution from within a single process.
+/// The `solutions` binary links every solution in `src/bin`, see `build.rs`.
+use std::io;
+use std::process::{ExitStatus, Stdio};
+
+use crate::template::cargo_command;
+use crate::template::report::PartReport;
+use crate::{Day, Year};
+
+/// A solution registered by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Runs both parts against an input, printing and returning their reports.
+    pub run: fn(&str) -> Vec<PartReport>,
+}
+
+/// Builds the `solutions` binary.
+pub fn build(is_release: bool) -> Result<ExitStatus, io::Error> {
+    let mut args = vec!["build", "--quiet", "--bin", "solutions"];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    cargo_command()
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+}
+
+/// Runs a command of the `solutions` binary, forwarding its output.
+pub fn run(command_args: &[String], is_release: bool) -> Result<ExitStatus, io::Error> {
+    let mut args: Vec<String> = vec![
+        "run".into(),
+        "--quiet".into(),
+        "--bin".into(),
+        "solutions".into(),
+    ];
+
+    if is_release {
+        args.push("--release".into());
+    }
+
+    args.push("--".into());
+    args.extend_from_slice(command_args);
+
+    cargo_command()
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+}
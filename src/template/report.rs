@@ -0,0 +1,494 @@
+/// Structured, machine-readable records of solution runs.
+///
+/// In `--format json` mode, every solution part is emitted as a single line of JSON:
+///
+/// ```json
+/// {"year":2023,"day":5,"part":1,"status":"solved","answer":"35","error":null,"samples":100,"outliers":2,
+///  "duration_ns":{"mean":..,"median":..,"std_dev":..,"min":..,"max":..,"p5":..,"p95":..}}
+/// ```
+///
+/// `answer` and `duration_ns` are `null` for parts that are not solved. Parts that returned an error have the status
+/// `failed` and its message in `error`, parts that panicked the status `panicked` and the location and message of
+/// the panic in `error`. `error` is `null` otherwise.
+/// Solutions that parse their input once emit an additional record for the parse phase, with `part` set to `0`.
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::Stats;
+use crate::{Day, Year};
+
+/// The part number of the parse phase of solutions that parse their input once, see [`PartReport::parse`].
+pub const PARSE_PART: u8 = 0;
+
+/// The output format of `solve` and `all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Human,
+    Json,
+}
+
+impl Format {
+    /// Reads the `--format` argument passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Human => f.write_str("human"),
+            Format::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a format of either \"human\" or \"json\"")
+    }
+}
+
+/// Prints a message that is not a report. Machine-readable output only holds reports, so it goes to stderr there.
+pub fn info(format: Format, message: &str) {
+    match format {
+        Format::Human => println!("{message}"),
+        Format::Json => eprintln!("{message}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of running a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    Failed,
+    Panicked,
+    /// The part did not run, because parsing the input failed.
+    Skipped,
+}
+
+impl Status {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+            Status::Skipped => "skipped",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "solved" => Some(Status::Solved),
+            "unsolved" => Some(Status::Unsolved),
+            "failed" => Some(Status::Failed),
+            "panicked" => Some(Status::Panicked),
+            "skipped" => Some(Status::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// The result of a solution part, see [`crate::template::runner::PartResult`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(String),
+    /// The part returned `None`, usually because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error, holds its message.
+    Failed(String),
+    /// The part panicked, holds the location and message of the panic.
+    Panicked(String),
+    /// The part did not run, holds the reason.
+    Skipped(String),
+}
+
+/// A record of a single solution part run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub stats: Option<Stats>,
+}
+
+impl PartReport {
+    /// Creates a report for a part run, `answer` is [`None`] if the part is not solved.
+    #[must_use]
+    pub fn new(year: Year, day: Day, part: u8, answer: Option<String>, stats: Stats) -> Self {
+        match answer {
+            Some(answer) => Self {
+                year,
+                day,
+                part,
+                status: Status::Solved,
+                answer: Some(answer),
+                error: None,
+                stats: Some(stats),
+            },
+            None => Self {
+                year,
+                day,
+                part,
+                status: Status::Unsolved,
+                answer: None,
+                error: None,
+                stats: None,
+            },
+        }
+    }
+
+    /// Creates a report for a part that returned an error.
+    #[must_use]
+    pub fn failed(year: Year, day: Day, part: u8, error: String) -> Self {
+        Self::with_error(year, day, part, Status::Failed, error)
+    }
+
+    /// Creates a report for a part that panicked, `error` describes the location and message of the panic.
+    #[must_use]
+    pub fn panicked(year: Year, day: Day, part: u8, error: String) -> Self {
+        Self::with_error(year, day, part, Status::Panicked, error)
+    }
+
+    /// Creates a report for a part that did not run, `reason` explains why.
+    #[must_use]
+    pub fn skipped(year: Year, day: Day, part: u8, reason: String) -> Self {
+        Self::with_error(year, day, part, Status::Skipped, reason)
+    }
+
+    fn with_error(year: Year, day: Day, part: u8, status: Status, error: String) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            status,
+            answer: None,
+            error: Some(error),
+            stats: None,
+        }
+    }
+
+    /// Creates a report for a part from its [`Outcome`].
+    #[must_use]
+    pub fn from_outcome(year: Year, day: Day, part: u8, outcome: Outcome, stats: Stats) -> Self {
+        match outcome {
+            Outcome::Solved(answer) => Self::new(year, day, part, Some(answer), stats),
+            Outcome::Unsolved => Self::new(year, day, part, None, stats),
+            Outcome::Failed(error) => Self::failed(year, day, part, error),
+            Outcome::Panicked(error) => Self::panicked(year, day, part, error),
+            Outcome::Skipped(reason) => Self::skipped(year, day, part, reason),
+        }
+    }
+
+    /// The [`Outcome`] of the reported part.
+    #[must_use]
+    pub fn outcome(&self) -> Outcome {
+        match (self.status, &self.answer, &self.error) {
+            (Status::Solved, Some(answer), _) => Outcome::Solved(answer.clone()),
+            (Status::Failed, _, error) => Outcome::Failed(error.clone().unwrap_or_default()),
+            (Status::Panicked, _, error) => Outcome::Panicked(error.clone().unwrap_or_default()),
+            (Status::Skipped, _, error) => Outcome::Skipped(error.clone().unwrap_or_default()),
+            _ => Outcome::Unsolved,
+        }
+    }
+
+    /// Creates a report for the parse phase of a solution, which has timings but no answer.
+    #[must_use]
+    pub fn parse(year: Year, day: Day, stats: Stats) -> Self {
+        Self {
+            year,
+            day,
+            part: PARSE_PART,
+            status: Status::Solved,
+            answer: None,
+            error: None,
+            stats: Some(stats),
+        }
+    }
+
+    /// Serializes the report into a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        #[allow(clippy::cast_precision_loss)]
+        let (samples, outliers) = self
+            .stats
+            .map_or((0.0, 0.0), |x| (x.samples as f64, x.outliers as f64));
+
+        let durations = self
+            .stats
+            .map_or_else(|| stringify(JsonValue::Null), |x| durations_to_json(&x));
+
+        ordered_object(&[
+            ("year", stringify(f64::from(self.year.into_inner()).into())),
+            ("day", stringify(f64::from(self.day.into_inner()).into())),
+            ("part", stringify(f64::from(self.part).into())),
+            ("status", stringify(self.status.as_str().to_string().into())),
+            (
+                "answer",
+                stringify(self.answer.clone().map_or(JsonValue::Null, JsonValue::from)),
+            ),
+            (
+                "error",
+                stringify(self.error.clone().map_or(JsonValue::Null, JsonValue::from)),
+            ),
+            ("samples", stringify(samples.into())),
+            ("outliers", stringify(outliers.into())),
+            ("duration_ns", durations),
+        ])
+    }
+
+    /// Deserializes a report from a line of JSON, returns [`None`] if the line is not a report.
+    #[must_use]
+    pub fn from_json(s: &str) -> Option<Self> {
+        let value: JsonValue = s.trim().parse().ok()?;
+        let map: &HashMap<String, JsonValue> = value.get()?;
+
+        let number = |key: &str| map.get(key).and_then(|x| x.get::<f64>()).copied();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = Year::new(number("year")? as u16)?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Day::new_in(year, number("day")? as u8)?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = number("part")? as u8;
+        let status = Status::parse(map.get("status")?.get::<String>()?)?;
+        let answer = map.get("answer").and_then(|x| x.get::<String>()).cloned();
+        let error = map.get("error").and_then(|x| x.get::<String>()).cloned();
+
+        let stats = match map.get("duration_ns").and_then(|x| x.get()) {
+            Some(durations) => {
+                let durations: &HashMap<String, JsonValue> = durations;
+                let duration = |key: &str| {
+                    durations
+                        .get(key)
+                        .and_then(|x| x.get::<f64>())
+                        .map(|x| nanos_to_duration(*x))
+                };
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Some(Stats {
+                    samples: number("samples")? as u128,
+                    outliers: number("outliers")? as u128,
+                    mean: duration("mean")?,
+                    median: duration("median")?,
+                    std_dev: duration("std_dev")?,
+                    min: duration("min")?,
+                    max: duration("max")?,
+                    p5: duration("p5")?,
+                    p95: duration("p95")?,
+                })
+            }
+            None => None,
+        };
+
+        Some(Self {
+            year,
+            day,
+            part,
+            status,
+            answer,
+            error,
+            stats,
+        })
+    }
+}
+
+pub(crate) fn stringify(value: JsonValue) -> String {
+    value
+        .stringify()
+        .expect("report values should always be serializable")
+}
+
+/// Joins serialized values into a JSON object, keeping keys in the given order.
+pub(crate) fn ordered_object(entries: &[(&str, String)]) -> String {
+    let fields: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| format!("{}:{value}", stringify((*key).to_string().into())))
+        .collect();
+
+    format!("{{{}}}", fields.join(","))
+}
+
+#[allow(clippy::cast_precision_loss)]
+/// Serializes the durations of [`Stats`] in nanoseconds.
+pub(crate) fn durations_to_json(stats: &Stats) -> String {
+    ordered_object(&[
+        ("mean", duration_to_json(stats.mean)),
+        ("median", duration_to_json(stats.median)),
+        ("std_dev", duration_to_json(stats.std_dev)),
+        ("min", duration_to_json(stats.min)),
+        ("max", duration_to_json(stats.max)),
+        ("p5", duration_to_json(stats.p5)),
+        ("p95", duration_to_json(stats.p95)),
+    ])
+}
+
+fn duration_to_json(duration: Duration) -> String {
+    stringify((duration.as_nanos() as f64).into())
+}
+
+fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_secs_f64(nanos / 1_000_000_000_f64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Format, Outcome, PartReport, Status, PARSE_PART};
+    use crate::template::runner::Stats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrip_solved() {
+        let mut stats = Stats::single(Duration::from_nanos(1500));
+        stats.samples = 200;
+        stats.outliers = 4;
+        stats.min = Duration::from_nanos(1200);
+
+        let report = PartReport::new(year!(2023), day!(2023, 5), 2, Some("46".into()), stats);
+        let parsed = PartReport::from_json(&report.to_json()).unwrap();
+
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.status, Status::Solved);
+    }
+
+    #[test]
+    fn roundtrip_unsolved() {
+        let report = PartReport::new(
+            year!(2023),
+            day!(2023, 1),
+            1,
+            None,
+            Stats::single(Duration::ZERO),
+        );
+        let parsed = PartReport::from_json(&report.to_json()).unwrap();
+
+        assert_eq!(parsed.status, Status::Unsolved);
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.stats, None);
+    }
+
+    #[test]
+    fn roundtrip_parse() {
+        let report = PartReport::parse(
+            year!(2023),
+            day!(2023, 8),
+            Stats::single(Duration::from_micros(30)),
+        );
+        let parsed = PartReport::from_json(&report.to_json()).unwrap();
+
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.part, PARSE_PART);
+    }
+
+    #[test]
+    fn roundtrip_failed() {
+        let report =
+            PartReport::failed(year!(2023), day!(2023, 4), 2, "invalid card: \"x\"".into());
+        let parsed = PartReport::from_json(&report.to_json()).unwrap();
+
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.status, Status::Failed);
+        assert_eq!(
+            parsed.outcome(),
+            Outcome::Failed("invalid card: \"x\"".into())
+        );
+    }
+
+    #[test]
+    fn roundtrip_panicked() {
+        let error = "src/bin/2023-07.rs:12:5: attempt to subtract with overflow";
+        let report = PartReport::panicked(year!(2023), day!(2023, 7), 1, error.into());
+        let parsed = PartReport::from_json(&report.to_json()).unwrap();
+
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.status, Status::Panicked);
+        assert_eq!(parsed.outcome(), Outcome::Panicked(error.into()));
+    }
+
+    #[test]
+    fn answers_with_special_characters() {
+        let answer = "#..#\n(1 samples)\n\"quoted\"";
+        let report = PartReport::new(
+            year!(2023),
+            day!(2023, 10),
+            1,
+            Some(answer.into()),
+            Stats::single(Duration::from_millis(1)),
+        );
+        let json = report.to_json();
+
+        assert_eq!(json.contains('\n'), false);
+        assert_eq!(
+            PartReport::from_json(&json).unwrap().answer.unwrap(),
+            answer
+        );
+    }
+
+    #[test]
+    fn stable_key_order() {
+        let report = PartReport::new(
+            year!(2023),
+            day!(2023, 2),
+            1,
+            None,
+            Stats::single(Duration::ZERO),
+        );
+        assert_eq!(
+            report.to_json(),
+            r#"{"year":2023,"day":2,"part":1,"status":"unsolved","answer":null,"error":null,"samples":0,"outliers":0,"duration_ns":null}"#
+        );
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(PartReport::from_json("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(PartReport::from_json("{\"foo\": 1}"), None);
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("human".parse::<Format>().unwrap(), Format::Human);
+        assert_eq!("xml".parse::<Format>().is_err(), true);
+    }
+}
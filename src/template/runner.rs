@@ -1,103 +1,360 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::input::InputSource;
+use crate::template::ledger::{self, Submission, Verdict};
+use crate::template::report::{self, Format, Outcome, PartReport, PARSE_PART};
+use crate::template::{answers, aoc_cli, aoc_client, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
+use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppresses all output of [`run_part`], for callers that present the returned reports themselves.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+thread_local! {
+    /// Whether a panic on this thread is caught by [`catch_panic`], which prints it itself.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// The description of the last panic caught by [`catch_panic`].
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `func`, returning a description of the location and message of the panic if it panics.
+///
+/// Panics outside of `catch_panic` are still printed by the default panic hook.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                CAUGHT.set(Some(describe_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.set(was_catching);
+
+    result.map_err(|_| CAUGHT.take().unwrap_or_else(|| "unknown panic".into()))
+}
+
+fn describe_panic(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    match info.location() {
+        Some(location) => format!("{location}: {message}"),
+        None => message.to_string(),
+    }
+}
+
+/// The return type of a solution part.
+///
+/// Parts return `Option<T>`, where [`None`] marks a part as not solved yet, or `Result<T, E>` to report why they
+/// could not compute an answer, e.g. because of malformed input.
+pub trait PartResult {
+    fn outcome(&self) -> Outcome;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Solved(answer.to_string()),
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) -> PartReport {
+    let format = Format::from_args();
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // a panicking part is reported, so the other part still runs.
+    let report = match catch_panic(|| {
+        run_timed(func, input, |result| {
+            if format == Format::Human && !is_quiet() {
+                print_result(&result.outcome(), &part_str, None);
+            }
+        })
+    }) {
+        Ok((result, stats)) => PartReport::from_outcome(year, day, part, result.outcome(), stats),
+        Err(panic) => PartReport::panicked(year, day, part, panic),
+    };
+
+    if !is_quiet() {
+        print_report(&report, format);
+    }
+
+    if let Some(answer) = &report.answer {
+        submit_result(answer, year, day, part);
+    }
+
+    report
+}
+
+/// Runs the `parse` function of a solution that parses its input once for both parts.
+/// Its timing is reported like a part, see [`PARSE_PART`].
+///
+/// Returns [`None`] instead of the parsed input if parsing panicked, the parts are then reported by [`skip_part`].
+pub fn run_parse<'a, T>(
+    func: impl Fn(&'a str) -> T,
+    input: &'a str,
+    year: Year,
+    day: Day,
+) -> (Option<T>, PartReport) {
+    let format = Format::from_args();
+
+    let (parsed, report) = match catch_panic(|| {
+        run_timed(func, input, |_| {
+            if format == Format::Human && !is_quiet() {
+                print!("Parse:");
+            }
+        })
+    }) {
+        Ok((parsed, stats)) => (Some(parsed), PartReport::parse(year, day, stats)),
+        Err(panic) => (None, PartReport::panicked(year, day, PARSE_PART, panic)),
+    };
+
+    if !is_quiet() {
+        print_report(&report, format);
+    }
+
+    (parsed, report)
+}
+
+/// Reports a part as skipped, for solutions whose input could not be parsed.
+pub fn skip_part(year: Year, day: Day, part: u8) -> PartReport {
+    let report = PartReport::skipped(year, day, part, "the input could not be parsed".into());
+
+    if !is_quiet() {
+        print_report(&report, Format::from_args());
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    report
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Prints the final result of a part in the given [`Format`].
+pub fn print_report(report: &PartReport, format: Format) {
+    match format {
+        Format::Human if report.part == PARSE_PART && report.error.is_some() => {
+            print_result(&report.outcome(), "Parse", Some(""));
+        }
+        Format::Human if report.part == PARSE_PART => {
+            let duration_str = report.stats.as_ref().map(format_duration);
+            print!("\r");
+            println!("Parse:{}", duration_str.unwrap_or_default());
+        }
+        Format::Human => {
+            let duration_str = report.stats.as_ref().map(format_duration);
+            print_result(
+                &report.outcome(),
+                &format!("Part {}", report.part),
+                Some(duration_str.as_deref().unwrap_or_default()),
+            );
+        }
+        Format::Json => println!("{}", report.to_json()),
     }
 }
 
+/// Summary statistics of the samples collected for a solution part.
+///
+/// All values except `samples` and `outliers` are computed after outlier rejection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// Number of measured samples, excluding warmup iterations.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p5: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Creates [`Stats`] for a part that was executed exactly once.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            outliers: 0,
+            mean: duration,
+            median: duration,
+            std_dev: Duration::ZERO,
+            min: duration,
+            max: duration,
+            p5: duration,
+            p95: duration,
+        }
+    }
+
+    /// Computes [`Stats`] from raw samples.
+    /// Samples outside of the Tukey fences (1.5 × IQR beyond the quartiles) are rejected as outliers.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats without samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25.0).as_secs_f64();
+        let q3 = percentile(&sorted, 75.0).as_secs_f64();
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (lower..=upper).contains(&x.as_secs_f64()))
+            .collect();
+
+        let mean = kept.iter().map(Duration::as_secs_f64).sum::<f64>() / kept.len() as f64;
+        let variance = kept
+            .iter()
+            .map(|x| (x.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / kept.len() as f64;
+
+        Self {
+            samples: sorted.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+            mean: Duration::from_secs_f64(mean),
+            median: percentile(&kept, 50.0),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            p5: percentile(&kept, 5.0),
+            p95: percentile(&kept, 95.0),
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Stats) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let result = black_box(func(black_box(input.clone())));
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if Format::from_args() == Format::Human && !is_quiet() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    // warm up caches and branch predictors before collecting samples.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
 
-    let mut timers: Vec<Duration> = vec![];
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
-        func(cloned);
+        black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} @ {} samples) [σ {:.1?}, min {:.1?}, p5 {:.1?}, p95 {:.1?}, max {:.1?}, outliers {}]",
+            stats.median,
+            stats.samples,
+            stats.std_dev,
+            stats.min,
+            stats.p5,
+            stats.p95,
+            stats.max,
+            stats.outliers
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+/// Prints a part result, `duration_str` is [`None`] for intermediate results that get overwritten once timing completes.
+fn print_result(outcome: &Outcome, part: &str, duration_str: Option<&str>) {
+    let is_intermediate_result = duration_str.is_none();
+    let duration_str = duration_str.unwrap_or_default();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match outcome {
+        Outcome::Solved(result) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -116,7 +373,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,21 +381,36 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(error) => {
+            let str = format!("{part}: ✖ {ANSI_BOLD}error:{ANSI_RESET} {error}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+            }
+        }
+        Outcome::Panicked(panic) => {
+            let str = format!("{part}: ✖ {ANSI_BOLD}panicked{ANSI_RESET} at {panic}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+            }
+        }
+        Outcome::Skipped(reason) => println!("{part}: ✖ skipped, {reason}"),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+fn submit_result(answer: &str, year: Year, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -154,14 +426,145 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
+    }
+
+    let source = InputSource::from_args();
+    if !source.is_puzzle() {
+        eprintln!(
+            "Refusing to submit {answer}: it was computed from {} instead of the puzzle input.",
+            source.label(year, day)
+        );
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let submissions = ledger::load(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read submission ledger: {e}");
         process::exit(1);
+    });
+
+    if let Err(refusal) = submissions.check(part, answer, ledger::now()) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
+        return;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let format = Format::from_args();
+    let response = if aoc_cli::check().is_ok() {
+        report::info(format, "Submitting result via aoc-cli...");
+        match aoc_cli::submit(year, day, part, answer) {
+            Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => {
+                let response = String::from_utf8_lossy(&output.stdout).into_owned();
+                report::info(format, response.trim_end());
+                response
+            }
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        report::info(format, "Submitting result...");
+        match aoc_client::submit(year, day, part, answer) {
+            Ok(response) => {
+                report::info(format, &response);
+                response
+            }
+            Err(e) => {
+                eprintln!("failed to submit result: {e}");
+                process::exit(1);
+            }
+        }
+    };
+
+    record_submission(
+        year,
+        day,
+        Submission::from_response(part, answer, &response),
+    );
+}
+
+/// Records a submission in the ledger and, if it was correct, its answer.
+fn record_submission(year: Year, day: Day, submission: Submission) {
+    if let Err(e) = ledger::record(year, day, &submission) {
+        eprintln!("Failed to record submission: {e}");
+    }
+
+    if submission.verdict != Verdict::Correct {
+        return;
+    }
+
+    match answers::save(year, day, submission.part, &submission.answer) {
+        Ok(()) => report::info(
+            Format::from_args(),
+            &format!(
+                "Recorded answer in \"{}\".",
+                answers::get_path_for_answers(year, day).display()
+            ),
+        ),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_parse, set_quiet, Stats};
+    use std::time::Duration;
+
+    use crate::template::report::{Status, PARSE_PART};
+    use crate::{day, year};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn stats_single() {
+        let stats = Stats::single(Duration::from_millis(3));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(&nanos(&[50, 10, 40, 20, 30]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.p5, Duration::from_nanos(10));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.std_dev.as_nanos(), 14);
+    }
+
+    #[test]
+    fn stats_reject_outliers() {
+        let stats = Stats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(102));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn parse_panic() {
+        set_quiet(true);
+
+        let (parsed, report) = run_parse(
+            |_: &str| -> u32 { panic!("bad input") },
+            "",
+            year!(2023),
+            day!(2023, 8),
+        );
+        assert_eq!(parsed, None);
+        assert_eq!(report.part, PARSE_PART);
+        assert_eq!(report.status, Status::Panicked);
+        assert!(report.error.unwrap().ends_with("bad input"));
+
+        let (parsed, report) = run_parse(|x: &str| x.len(), "abc", year!(2023), day!(2023, 8));
+        assert_eq!(parsed, Some(3));
+        assert_eq!(report.status, Status::Solved);
+    }
 }
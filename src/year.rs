@@ -0,0 +1,152 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{AllDays, MAX_DAYS};
+
+/// The year of the first Advent of Code event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// A valid Advent of Code event year (i.e. an integer from 2015 onwards).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// The number of days of the event in this year.
+    ///
+    /// Events up to 2024 ran for 25 days, later events for 12. The length of any year can be overridden with
+    /// an `AOC_DAYS_YYYY` environment variable, e.g. `AOC_DAYS_2026 = "12"` in `.cargo/config.toml`.
+    pub fn days(self) -> u8 {
+        env::var(format!("AOC_DAYS_{self}"))
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .filter(|x| (1..=MAX_DAYS).contains(x))
+            .unwrap_or_else(|| Self::known_days(self.0))
+    }
+
+    /// The number of days of the event in `year`, as announced for every year so far.
+    ///
+    /// Unlike [`Year::days`] this ignores the `AOC_DAYS_YYYY` override, so it can be used in a const context,
+    /// e.g. by the [`day!`](crate::day) macro.
+    pub const fn known_days(year: u16) -> u8 {
+        if year >= 2025 {
+            12
+        } else {
+            MAX_DAYS
+        }
+    }
+
+    /// An iterator that yields every day of the event in this year.
+    pub fn all_days(self) -> AllDays {
+        AllDays::of(self)
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable, see `.cargo/config.toml`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting an event year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= $crate::FIRST_YEAR && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting an event year of 2015 or later"
+            ),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parse_year() {
+        assert_eq!("2023".parse::<Year>().unwrap(), 2023);
+        assert_eq!("2014".parse::<Year>().is_err(), true);
+        assert_eq!("23".parse::<Year>().is_err(), true);
+    }
+
+    #[test]
+    fn days_of_event() {
+        assert_eq!(Year::new(2015).unwrap().days(), 25);
+        assert_eq!(Year::new(2024).unwrap().days(), 25);
+        assert_eq!(Year::new(2025).unwrap().days(), 12);
+    }
+}